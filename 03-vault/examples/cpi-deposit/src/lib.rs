@@ -0,0 +1,79 @@
+//! How another program pays lamports into a user's vault with the generated
+//! CPI helpers from `vault = { features = ["cpi"] }`.
+
+use anchor_lang::prelude::*;
+use vault::{
+    cpi::{accounts::DepositFor, deposit_for},
+    program::MyProgram,
+};
+
+/// Accounts a calling program forwards to `deposit_for`. The vault PDAs are
+/// checked by the vault program itself.
+pub struct PayIntoVault<'a, 'info> {
+    pub vault_program: &'a Program<'info, MyProgram>,
+    pub payer: &'a AccountInfo<'info>,
+    pub owner: &'a AccountInfo<'info>,
+    pub vault_state: &'a AccountInfo<'info>,
+    pub vault: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> PayIntoVault<'_, 'info> {
+    pub fn cpi_accounts(&self) -> DepositFor<'info> {
+        DepositFor {
+            payer: self.payer.clone(),
+            owner: self.owner.clone(),
+            vault_state: self.vault_state.clone(),
+            vault: self.vault.clone(),
+            system_program: self.system_program.clone(),
+        }
+    }
+
+    /// Pay `amount` lamports into the owner's vault. Pass the caller's PDA
+    /// seeds when `payer` is a PDA, or an empty slice when it signed the
+    /// outer transaction.
+    pub fn pay(&self, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.vault_program.to_account_info(),
+            self.cpi_accounts(),
+            signer_seeds,
+        );
+        deposit_for(cpi_ctx, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpi_accounts_match_deposit_for() {
+        let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports: Vec<u64> = vec![0; 5];
+        let mut data: Vec<Vec<u8>> = vec![vec![]; 5];
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &keys[0], false, 0)
+            })
+            .collect();
+
+        let accounts = DepositFor {
+            payer: infos[0].clone(),
+            owner: infos[1].clone(),
+            vault_state: infos[2].clone(),
+            vault: infos[3].clone(),
+            system_program: infos[4].clone(),
+        };
+        let metas = accounts.to_account_metas(None);
+
+        // Same order and flags the program's `DepositFor` expects
+        assert_eq!(metas.len(), 5);
+        assert!(metas[0].is_signer && metas[0].is_writable);
+        assert!(!metas[1].is_signer && !metas[1].is_writable);
+        assert!(metas[2].is_writable && metas[3].is_writable);
+        assert_eq!(metas[4].pubkey, keys[4]);
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("Token accounts must be passed as (mint, vault_ata, signer_ata) triplets.")]
+    InvalidRemainingAccounts,
+    #[msg("Token account does not belong to the vault or signer for this mint.")]
+    InvalidTokenAccount,
+    #[msg("Vault is locked until its unlock time.")]
+    VaultLocked,
+    #[msg("Unlock time must be in the future and later than the current lock.")]
+    InvalidUnlockTime,
+    #[msg("Insufficient funds in the vault.")]
+    InsufficientFunds,
+    #[msg("Deposit would leave the vault below the rent-exempt minimum.")]
+    DepositBelowRentExemption,
+    #[msg("Withdrawal would leave the vault with less than the rent-exempt minimum.")]
+    WithdrawalLeavesDust,
+    #[msg("Overflow detected.")]
+    Overflow,
+    #[msg("Multisig needs between one and ten owners.")]
+    InvalidOwners,
+    #[msg("Multisig owners must be unique.")]
+    DuplicateOwner,
+    #[msg("Threshold must be between one and the number of owners.")]
+    InvalidThreshold,
+    #[msg("Signer is not an owner of the multisig.")]
+    NotAnOwner,
+    #[msg("Signer has already approved this proposal.")]
+    AlreadyApproved,
+    #[msg("Proposal has not reached the approval threshold.")]
+    ThresholdNotMet,
+    #[msg("Withdrawal exceeds the delegate's allowance for this period.")]
+    AllowanceExceeded,
+    #[msg("Every delegation of the vault must lead the remaining accounts.")]
+    MissingDelegations,
+    #[msg("Every vault token account holding tokens must be passed in the remaining accounts.")]
+    MissingTokenAccounts,
+    #[msg("Stream end time must be in the future.")]
+    InvalidStreamEnd,
+    #[msg("Nothing has vested in this stream yet.")]
+    NothingToClaim,
+    #[msg("Vault still has active streams.")]
+    ActiveStreams,
+    #[msg("Vault must keep its rent exempt minimum while streams are open.")]
+    StreamRentReserved,
+    #[msg("Guardian set needs between one and ten unique guardians.")]
+    InvalidGuardians,
+    #[msg("Recovery delay cannot be negative.")]
+    InvalidRecoveryDelay,
+    #[msg("Signer is not a guardian of the vault.")]
+    NotAGuardian,
+    #[msg("Recovery has not been approved or its timelock has not expired.")]
+    RecoveryNotReady,
+    #[msg("A recovery is pending, veto it first.")]
+    RecoveryPending,
+    #[msg("Underflow detected.")]
+    Underflow,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct Deposited {
+    pub vault_state: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub vault_state: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct Closed {
+    pub vault_state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{GuardianSet, Recovery},
+};
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    pub guardian: Signer<'info>,
+    #[account(
+        seeds = [b"guardians", guardian_set.vault_state.as_ref()],
+        bump = guardian_set.bump,
+        constraint = guardian_set.is_guardian(&guardian.key()) @ VaultError::NotAGuardian
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(
+        mut,
+        seeds = [b"recovery", guardian_set.vault_state.as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+}
+
+impl<'info> ApproveRecovery<'info> {
+    pub fn approve(&mut self) -> Result<()> {
+        let guardian = self.guardian.key();
+        require!(
+            !self.recovery.approvals.contains(&guardian),
+            VaultError::AlreadyApproved
+        );
+
+        self.recovery.approve(guardian, &self.guardian_set)
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{MultisigVault, Proposal},
+};
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.multisig_bump,
+        constraint = multisig.is_owner(&owner.key()) @ VaultError::NotAnOwner
+    )]
+    pub multisig: Account<'info, MultisigVault>,
+    #[account(
+        mut,
+        seeds = [b"proposal", multisig.key().as_ref(), proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = multisig
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+impl<'info> ApproveWithdrawal<'info> {
+    pub fn approve(&mut self) -> Result<()> {
+        let owner = self.owner.key();
+        require!(
+            !self.proposal.approvals.contains(&owner),
+            VaultError::AlreadyApproved
+        );
+
+        self.proposal.approvals.push(owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    error::VaultError,
+    events::Withdrawn,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"stream",
+            vault_state.key().as_ref(),
+            recipient.key().as_ref(),
+            stream.id.to_le_bytes().as_ref()
+        ],
+        bump = stream.bump,
+        has_one = vault_state,
+        has_one = recipient,
+        close = signer // Closes the stream and returns its rent to the owner
+    )]
+    pub stream: Account<'info, Stream>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelStream<'info> {
+    pub fn cancel(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vested = self.stream.vested_at(now);
+        let claimable = self.stream.claimable_at(now);
+
+        // Whatever vested is still owed to the recipient
+        if claimable > 0 {
+            self.vault_state
+                .release_stream(self.vault.lamports(), claimable)?;
+
+            let vault_state_key = self.vault_state.to_account_info().key();
+            let seeds = &[
+                b"vault".as_ref(),
+                vault_state_key.as_ref(),
+                &[self.vault_state.vault_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.recipient.to_account_info(),
+            };
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            transfer(cpi_ctx, claimable)?;
+
+            emit!(Withdrawn {
+                vault_state: self.vault_state.key(),
+                recipient: self.recipient.key(),
+                amount: claimable,
+                balance: self.vault_state.balance,
+                total_withdrawn: self.vault_state.total_withdrawn,
+            });
+        }
+
+        // The unvested remainder goes back to the owner's balance
+        let unvested = self.stream.total - vested;
+        self.vault_state.streamed -= unvested;
+        self.vault_state.balance = self
+            .vault_state
+            .balance
+            .checked_add(unvested)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    error::VaultError,
+    events::Withdrawn,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    pub owner: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"stream",
+            vault_state.key().as_ref(),
+            recipient.key().as_ref(),
+            stream.id.to_le_bytes().as_ref()
+        ],
+        bump = stream.bump,
+        has_one = vault_state,
+        has_one = recipient
+    )]
+    pub stream: Account<'info, Stream>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimStream<'info> {
+    pub fn claim(&mut self) -> Result<()> {
+        let amount = self.stream.claimable_at(Clock::get()?.unix_timestamp);
+        require!(amount > 0, VaultError::NothingToClaim);
+
+        self.vault_state
+            .release_stream(self.vault.lamports(), amount)?;
+        self.stream.withdrawn += amount;
+
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recipient.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        transfer(cpi_ctx, amount)?;
+
+        emit!(Withdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.recipient.key(),
+            amount,
+            balance: self.vault_state.balance,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+        Ok(())
+    }
+}
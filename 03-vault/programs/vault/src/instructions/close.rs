@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{
+    error::VaultError,
+    events::Closed,
+    state::{Delegation, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump,
+        close = signer // Closes vault_state and sends lamports to signer
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    /// CHECK: The vault's guardian set PDA, closed with the vault when it exists
+    #[account(
+        mut,
+        seeds = [b"guardians", vault_state.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: UncheckedAccount<'info>,
+    /// CHECK: The vault's recovery PDA, which must not exist when the vault closes
+    #[account(
+        seeds = [b"recovery", vault_state.key().as_ref()],
+        bump
+    )]
+    pub recovery: UncheckedAccount<'info>,
+    // Token accounts are swept through the program that owns their mint
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Close<'info> {
+    // Allowances are bound to the vault state address, so they must not outlive it
+    pub fn close_delegations(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<&'info [AccountInfo<'info>]> {
+        Delegation::close_all(
+            &self.vault_state,
+            remaining_accounts,
+            &self.signer.to_account_info(),
+        )
+    }
+
+    pub fn sweep_token_accounts(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            remaining_accounts.chunks_exact(3).remainder().is_empty(),
+            VaultError::InvalidRemainingAccounts
+        );
+
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let mut swept = 0;
+        for accounts in remaining_accounts.chunks(3) {
+            let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+            let vault_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+            let signer_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+
+            let token_program =
+                token_program_for(&accounts[0], &self.token_program, &self.token_2022_program)?;
+            require_keys_eq!(
+                vault_ata.key(),
+                get_associated_token_address_with_program_id(
+                    &self.vault.key(),
+                    &mint.key(),
+                    &token_program.key()
+                ),
+                VaultError::InvalidTokenAccount
+            );
+            require_keys_eq!(
+                signer_ata.key(),
+                get_associated_token_address_with_program_id(
+                    &self.signer.key(),
+                    &mint.key(),
+                    &token_program.key()
+                ),
+                VaultError::InvalidTokenAccount
+            );
+
+            // Transfer the remaining tokens from the vault ATA to the signer
+            if vault_ata.amount > 0 {
+                swept += 1;
+                let cpi_accounts = TransferChecked {
+                    from: vault_ata.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: signer_ata.to_account_info(),
+                    authority: self.vault.to_account_info(),
+                };
+                let cpi_program = token_program.to_account_info();
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+                transfer_checked(cpi_ctx, vault_ata.amount, mint.decimals)?;
+            }
+
+            // Close the vault ATA and return its rent to the signer
+            let cpi_accounts = CloseAccount {
+                account: vault_ata.to_account_info(),
+                destination: self.signer.to_account_info(),
+                authority: self.vault.to_account_info(),
+            };
+            let cpi_program = token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            close_account(cpi_ctx)?;
+        }
+
+        // Tokens left in an account that wasn't passed would be stranded once the vault closes
+        require!(
+            swept >= self.vault_state.token_accounts,
+            VaultError::MissingTokenAccounts
+        );
+        Ok(())
+    }
+
+    pub fn close(&mut self) -> Result<()> {
+        self.vault_state.check_unlocked()?;
+        require!(self.vault_state.streamed == 0, VaultError::ActiveStreams);
+        // A pending recovery would be left pointing at a closed vault, the owner vetoes it first
+        require!(
+            self.recovery.owner != &crate::ID,
+            VaultError::RecoveryPending
+        );
+
+        // The guardian set is bound to the vault state address, so it must not outlive it
+        if self.guardian_set.owner == &crate::ID {
+            let guardian_set = self.guardian_set.to_account_info();
+            let signer = self.signer.to_account_info();
+            **signer.try_borrow_mut_lamports()? = signer
+                .lamports()
+                .checked_add(guardian_set.lamports())
+                .ok_or(VaultError::Overflow)?;
+            **guardian_set.try_borrow_mut_lamports()? = 0;
+            guardian_set.assign(&System::id());
+            guardian_set.realloc(0, false)?;
+        }
+
+        // Transfer remaining lamports from vault to signer
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let lamports = self.vault.lamports();
+        if lamports > 0 {
+            let cpi_accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.signer.to_account_info(),
+            };
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            transfer(cpi_ctx, lamports)?;
+        }
+
+        emit!(Closed {
+            vault_state: self.vault_state.key(),
+            owner: self.signer.key(),
+            amount: lamports,
+            total_deposited: self.vault_state.total_deposited,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+
+        // The vault_state account is closed automatically via the `close = signer` constraint
+        Ok(())
+    }
+}
+
+// Mints are owned by either token program, pick the one the CPIs for this mint must go through
+pub fn token_program_for<'info>(
+    mint: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    token_2022_program: &Program<'info, Token2022>,
+) -> Result<AccountInfo<'info>> {
+    if mint.owner == &token_program.key() {
+        Ok(token_program.to_account_info())
+    } else if mint.owner == &token_2022_program.key() {
+        Ok(token_2022_program.to_account_info())
+    } else {
+        err!(VaultError::InvalidTokenAccount)
+    }
+}
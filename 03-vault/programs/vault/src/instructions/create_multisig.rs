@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{MultisigVault, MAX_OWNERS},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"multisig", creator.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump,
+        space = MultisigVault::INIT_SPACE
+    )]
+    pub multisig: Account<'info, MultisigVault>,
+    #[account(
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMultisig<'info> {
+    pub fn create_multisig(
+        &mut self,
+        seed: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+        bumps: &CreateMultisigBumps,
+    ) -> Result<()> {
+        require!(
+            !owners.is_empty() && owners.len() <= MAX_OWNERS,
+            VaultError::InvalidOwners
+        );
+        for (i, owner) in owners.iter().enumerate() {
+            require!(!owners[..i].contains(owner), VaultError::DuplicateOwner);
+        }
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            VaultError::InvalidThreshold
+        );
+
+        self.multisig.set_inner(MultisigVault {
+            creator: self.creator.key(),
+            seed,
+            owners,
+            threshold,
+            proposal_count: 0,
+            multisig_bump: bumps.multisig,
+            vault_bump: bumps.vault,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{Stream, VaultState},
+};
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey, id: u64)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"stream", vault_state.key().as_ref(), recipient.as_ref(), id.to_le_bytes().as_ref()],
+        bump,
+        space = Stream::INIT_SPACE
+    )]
+    pub stream: Account<'info, Stream>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStream<'info> {
+    pub fn create_stream(
+        &mut self,
+        recipient: Pubkey,
+        id: u64,
+        rate: u64,
+        end_time: i64,
+        bumps: &CreateStreamBumps,
+    ) -> Result<()> {
+        self.vault_state.check_unlocked()?;
+        require!(rate > 0, VaultError::InvalidAmount);
+
+        let start_time = Clock::get()?.unix_timestamp;
+        require!(end_time > start_time, VaultError::InvalidStreamEnd);
+
+        let total = rate
+            .checked_mul((end_time - start_time) as u64)
+            .ok_or(VaultError::Overflow)?;
+
+        // Reserve the whole stream so the owner can't withdraw it while it vests, and keep
+        // the rent exempt minimum in the balance so payouts never leave the vault with dust
+        let reserved = total
+            .checked_add(Rent::get()?.minimum_balance(0))
+            .ok_or(VaultError::Overflow)?;
+        require!(
+            reserved <= self.vault_state.balance,
+            VaultError::InsufficientFunds
+        );
+        self.vault_state.balance -= total;
+        self.vault_state.streamed = self
+            .vault_state
+            .streamed
+            .checked_add(total)
+            .ok_or(VaultError::Overflow)?;
+
+        self.stream.set_inner(Stream {
+            vault_state: self.vault_state.key(),
+            recipient,
+            id,
+            rate,
+            start_time,
+            end_time,
+            total,
+            withdrawn: 0,
+            bump: bumps.stream,
+        });
+        Ok(())
+    }
+}
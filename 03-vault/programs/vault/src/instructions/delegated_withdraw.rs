@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    events::Withdrawn,
+    state::{Delegation, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct DelegatedWithdraw<'info> {
+    pub delegate: Signer<'info>,
+    pub owner: SystemAccount<'info>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"delegation", vault_state.key().as_ref(), delegate.key().as_ref()],
+        bump = delegation.bump,
+        has_one = vault_state,
+        has_one = delegate
+    )]
+    pub delegation: Account<'info, Delegation>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelegatedWithdraw<'info> {
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.check_unlocked()?;
+        self.delegation.spend(amount)?;
+        self.vault_state.debit(self.vault.lamports(), amount)?;
+
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recipient.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        transfer(cpi_ctx, amount)?;
+
+        emit!(Withdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.recipient.key(),
+            amount,
+            balance: self.vault_state.balance,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{events::Deposited, state::VaultState};
+
+// Lets anyone, including other programs through CPI, pay into an owner's vault
+// (see examples/cpi-deposit for a caller built against the `cpi` feature)
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", owner.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositFor<'info> {
+    pub fn deposit_for(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.credit(self.vault.lamports(), amount)?;
+
+        let cpi_accounts = Transfer {
+            from: self.payer.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer(cpi_ctx, amount)?;
+
+        emit!(Deposited {
+            vault_state: self.vault_state.key(),
+            depositor: self.payer.key(),
+            amount,
+            balance: self.vault_state.balance,
+            total_deposited: self.vault_state.total_deposited,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::state::{MultisigVault, VaultState};
+
+#[derive(Accounts)]
+pub struct DepositMultisig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.multisig_bump
+    )]
+    pub multisig: Account<'info, MultisigVault>,
+    #[account(
+        mut,
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump = multisig.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositMultisig<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        VaultState::check_deposit(self.vault.lamports(), amount)?;
+
+        let cpi_accounts = Transfer {
+            from: self.signer.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer(cpi_ctx, amount)?;
+        Ok(())
+    }
+}
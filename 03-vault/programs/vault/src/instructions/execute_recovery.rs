@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked,
+    },
+};
+
+use crate::{
+    error::VaultError,
+    events::Closed,
+    instructions::token_program_for,
+    state::{Delegation, GuardianSet, Recovery, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    pub old_owner: SystemAccount<'info>,
+    #[account(mut)]
+    pub initiator: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", old_owner.key().as_ref()],
+        bump = old_vault_state.state_bump,
+        close = new_owner
+    )]
+    pub old_vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", old_vault_state.key().as_ref()],
+        bump = old_vault_state.vault_bump
+    )]
+    pub old_vault: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = new_owner,
+        seeds = [b"state", new_owner.key().as_ref()],
+        bump,
+        space = VaultState::INIT_SPACE
+    )]
+    pub new_vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", new_vault_state.key().as_ref()],
+        bump
+    )]
+    pub new_vault: SystemAccount<'info>,
+    // Guardians are not carried over, the new owner appoints their own
+    #[account(
+        mut,
+        seeds = [b"guardians", old_vault_state.key().as_ref()],
+        bump = guardian_set.bump,
+        close = new_owner
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(
+        mut,
+        seeds = [b"recovery", old_vault_state.key().as_ref()],
+        bump = recovery.bump,
+        has_one = new_owner,
+        has_one = initiator,
+        close = initiator
+    )]
+    pub recovery: Account<'info, Recovery>,
+    // Token accounts are migrated through the program that owns their mint
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteRecovery<'info> {
+    pub fn migrate(&mut self, bumps: &ExecuteRecoveryBumps) -> Result<()> {
+        let ready_at = self.recovery.ready_at.ok_or(VaultError::RecoveryNotReady)?;
+        require!(
+            Clock::get()?.unix_timestamp >= ready_at,
+            VaultError::RecoveryNotReady
+        );
+        // Streams are bound to the old vault state, they have to be fully claimed first
+        require!(
+            self.old_vault_state.streamed == 0,
+            VaultError::ActiveStreams
+        );
+
+        self.new_vault_state.set_inner(VaultState {
+            vault_bump: bumps.new_vault,
+            state_bump: bumps.new_vault_state,
+            unlock_at: self.old_vault_state.unlock_at,
+            balance: self.old_vault_state.balance,
+            streamed: 0,
+            total_deposited: self.old_vault_state.total_deposited,
+            total_withdrawn: self.old_vault_state.total_withdrawn,
+            delegations: 0,
+            token_accounts: 0,
+        });
+
+        // Move every lamport held by the old vault to the new one
+        let lamports = self.old_vault.lamports();
+        if lamports > 0 {
+            let old_vault_state_key = self.old_vault_state.key();
+            let vault_seeds = &[
+                b"vault".as_ref(),
+                old_vault_state_key.as_ref(),
+                &[self.old_vault_state.vault_bump],
+            ];
+            let signer_seeds = &[&vault_seeds[..]];
+            let cpi_accounts = Transfer {
+                from: self.old_vault.to_account_info(),
+                to: self.new_vault.to_account_info(),
+            };
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            transfer(cpi_ctx, lamports)?;
+        }
+
+        emit!(Closed {
+            vault_state: self.old_vault_state.key(),
+            owner: self.old_owner.key(),
+            amount: lamports,
+            total_deposited: self.old_vault_state.total_deposited,
+            total_withdrawn: self.old_vault_state.total_withdrawn,
+        });
+        Ok(())
+    }
+
+    // Delegates of the old owner keep no allowance on the new vault
+    pub fn close_delegations(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<&'info [AccountInfo<'info>]> {
+        Delegation::close_all(
+            &self.old_vault_state,
+            remaining_accounts,
+            &self.new_owner.to_account_info(),
+        )
+    }
+
+    pub fn migrate_token_accounts(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            remaining_accounts.chunks_exact(3).remainder().is_empty(),
+            VaultError::InvalidRemainingAccounts
+        );
+
+        let old_vault_state_key = self.old_vault_state.key();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            old_vault_state_key.as_ref(),
+            &[self.old_vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let mut migrated = 0;
+        for accounts in remaining_accounts.chunks(3) {
+            let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+            let old_vault_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+            let new_vault_ata = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+
+            let token_program =
+                token_program_for(&accounts[0], &self.token_program, &self.token_2022_program)?;
+            require_keys_eq!(
+                old_vault_ata.key(),
+                get_associated_token_address_with_program_id(
+                    &self.old_vault.key(),
+                    &mint.key(),
+                    &token_program.key()
+                ),
+                VaultError::InvalidTokenAccount
+            );
+            require_keys_eq!(
+                new_vault_ata.key(),
+                get_associated_token_address_with_program_id(
+                    &self.new_vault.key(),
+                    &mint.key(),
+                    &token_program.key()
+                ),
+                VaultError::InvalidTokenAccount
+            );
+
+            // Transfer the tokens from the old vault ATA to the new vault ATA
+            if old_vault_ata.amount > 0 {
+                migrated += 1;
+                let cpi_accounts = TransferChecked {
+                    from: old_vault_ata.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: new_vault_ata.to_account_info(),
+                    authority: self.old_vault.to_account_info(),
+                };
+                let cpi_program = token_program.to_account_info();
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+                transfer_checked(cpi_ctx, old_vault_ata.amount, mint.decimals)?;
+            }
+
+            // Close the old vault ATA and return its rent to the new owner
+            let cpi_accounts = CloseAccount {
+                account: old_vault_ata.to_account_info(),
+                destination: self.new_owner.to_account_info(),
+                authority: self.old_vault.to_account_info(),
+            };
+            let cpi_program = token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            close_account(cpi_ctx)?;
+        }
+
+        // The old vault state is closed with this instruction, so every token account must move now
+        require!(
+            migrated >= self.old_vault_state.token_accounts,
+            VaultError::MissingTokenAccounts
+        );
+        self.new_vault_state.token_accounts = migrated;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    error::VaultError,
+    state::{MultisigVault, Proposal, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub proposer: SystemAccount<'info>,
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    #[account(
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.multisig_bump
+    )]
+    pub multisig: Account<'info, MultisigVault>,
+    #[account(
+        mut,
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump = multisig.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"proposal", multisig.key().as_ref(), proposal.index.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = multisig,
+        has_one = proposer,
+        has_one = recipient,
+        close = proposer // Returns the proposal rent to the proposer once executed
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExecuteWithdrawal<'info> {
+    pub fn execute(&mut self) -> Result<()> {
+        require!(
+            self.proposal.approvals.len() >= self.multisig.threshold as usize,
+            VaultError::ThresholdNotMet
+        );
+
+        let amount = self.proposal.amount;
+        VaultState::check_withdrawal(self.vault.lamports(), amount)?;
+
+        let multisig_key = self.multisig.key();
+        let seeds = &[
+            b"vault".as_ref(),
+            multisig_key.as_ref(),
+            &[self.multisig.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.recipient.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        transfer(cpi_ctx, amount)?;
+        Ok(())
+    }
+}
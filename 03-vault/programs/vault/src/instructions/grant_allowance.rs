@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{AllowancePeriod, Delegation, VaultState},
+};
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct GrantAllowance<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        seeds = [b"delegation", vault_state.key().as_ref(), delegate.as_ref()],
+        bump,
+        space = Delegation::INIT_SPACE
+    )]
+    pub delegation: Account<'info, Delegation>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GrantAllowance<'info> {
+    // Granting again replaces the allowance and starts a fresh period
+    pub fn grant(
+        &mut self,
+        delegate: Pubkey,
+        allowance: u64,
+        period: AllowancePeriod,
+        bumps: &GrantAllowanceBumps,
+    ) -> Result<()> {
+        // A freshly created delegation is zeroed, count it so close can find it
+        if self.delegation.vault_state == Pubkey::default() {
+            self.vault_state.delegations = self
+                .vault_state
+                .delegations
+                .checked_add(1)
+                .ok_or(VaultError::Overflow)?;
+        }
+
+        self.delegation.set_inner(Delegation {
+            vault_state: self.vault_state.key(),
+            delegate,
+            allowance,
+            period,
+            current_period: period.current(&Clock::get()?),
+            spent: 0,
+            bump: bumps.delegation,
+        });
+        Ok(())
+    }
+}
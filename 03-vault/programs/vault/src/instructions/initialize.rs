@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"state", signer.key().as_ref()],
+        bump,
+        space = VaultState::INIT_SPACE
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialise(&mut self, bumps: &InitializeBumps) -> Result<()> {
+        self.vault_state.vault_bump = bumps.vault;
+        self.vault_state.state_bump = bumps.vault_state;
+        self.vault_state.unlock_at = None;
+        self.vault_state.balance = 0;
+        self.vault_state.streamed = 0;
+        self.vault_state.total_deposited = 0;
+        self.vault_state.total_withdrawn = 0;
+        self.vault_state.delegations = 0;
+        self.vault_state.token_accounts = 0;
+        Ok(())
+    }
+}
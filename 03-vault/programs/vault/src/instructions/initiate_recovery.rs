@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{GuardianSet, Recovery},
+};
+
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+    #[account(
+        seeds = [b"guardians", guardian_set.vault_state.as_ref()],
+        bump = guardian_set.bump,
+        constraint = guardian_set.is_guardian(&guardian.key()) @ VaultError::NotAGuardian
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    #[account(
+        init,
+        payer = guardian,
+        seeds = [b"recovery", guardian_set.vault_state.as_ref()],
+        bump,
+        space = Recovery::INIT_SPACE
+    )]
+    pub recovery: Account<'info, Recovery>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitiateRecovery<'info> {
+    pub fn initiate(&mut self, new_owner: Pubkey, bumps: &InitiateRecoveryBumps) -> Result<()> {
+        self.recovery.set_inner(Recovery {
+            vault_state: self.guardian_set.vault_state,
+            initiator: self.guardian.key(),
+            new_owner,
+            approvals: Vec::new(),
+            ready_at: None,
+            bump: bumps.recovery,
+        });
+
+        // The initiating guardian approves the recovery
+        self.recovery
+            .approve(self.guardian.key(), &self.guardian_set)
+    }
+}
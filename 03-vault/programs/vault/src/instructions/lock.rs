@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::VaultError, state::VaultState};
+
+#[derive(Accounts)]
+pub struct Lock<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+impl<'info> Lock<'info> {
+    pub fn lock(&mut self, unlock_at: i64) -> Result<()> {
+        require!(
+            unlock_at > Clock::get()?.unix_timestamp,
+            VaultError::InvalidUnlockTime
+        );
+        // An existing lock can only be extended, never shortened
+        if let Some(current) = self.vault_state.unlock_at {
+            require!(unlock_at > current, VaultError::InvalidUnlockTime);
+        }
+        self.vault_state.unlock_at = Some(unlock_at);
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+pub mod initialize;
+pub use initialize::*;
+
+pub mod payment;
+pub use payment::*;
+
+pub mod deposit_for;
+pub use deposit_for::*;
+
+pub mod payment_spl;
+pub use payment_spl::*;
+
+pub mod payment_nft;
+pub use payment_nft::*;
+
+pub mod lock;
+pub use lock::*;
+
+pub mod create_multisig;
+pub use create_multisig::*;
+
+pub mod deposit_multisig;
+pub use deposit_multisig::*;
+
+pub mod propose_withdrawal;
+pub use propose_withdrawal::*;
+
+pub mod approve_withdrawal;
+pub use approve_withdrawal::*;
+
+pub mod execute_withdrawal;
+pub use execute_withdrawal::*;
+
+pub mod grant_allowance;
+pub use grant_allowance::*;
+
+pub mod revoke_allowance;
+pub use revoke_allowance::*;
+
+pub mod delegated_withdraw;
+pub use delegated_withdraw::*;
+
+pub mod create_stream;
+pub use create_stream::*;
+
+pub mod claim_stream;
+pub use claim_stream::*;
+
+pub mod cancel_stream;
+pub use cancel_stream::*;
+
+pub mod set_guardians;
+pub use set_guardians::*;
+
+pub mod initiate_recovery;
+pub use initiate_recovery::*;
+
+pub mod approve_recovery;
+pub use approve_recovery::*;
+
+pub mod veto_recovery;
+pub use veto_recovery::*;
+
+pub mod execute_recovery;
+pub use execute_recovery::*;
+
+pub mod close;
+pub use close::*;
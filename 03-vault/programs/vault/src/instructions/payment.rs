@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    events::{Deposited, Withdrawn},
+    state::VaultState,
+};
+
+#[derive(Accounts)]
+pub struct Payment<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Payment<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.credit(self.vault.lamports(), amount)?;
+
+        let cpi_accounts = Transfer {
+            from: self.signer.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer(cpi_ctx, amount)?;
+
+        emit!(Deposited {
+            vault_state: self.vault_state.key(),
+            depositor: self.signer.key(),
+            amount,
+            balance: self.vault_state.balance,
+            total_deposited: self.vault_state.total_deposited,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.check_unlocked()?;
+        self.vault_state.debit(self.vault.lamports(), amount)?;
+
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.signer.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        transfer(cpi_ctx, amount)?;
+
+        emit!(Withdrawn {
+            vault_state: self.vault_state.key(),
+            recipient: self.signer.key(),
+            amount,
+            balance: self.vault_state.balance,
+            total_withdrawn: self.vault_state.total_withdrawn,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct PaymentNft<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    // Only master editions are accepted, which guarantees a supply of one
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: Account<'info, MasterEditionAccount>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PaymentNft<'info> {
+    pub fn deposit_nft(&mut self) -> Result<()> {
+        if self.vault_ata.amount == 0 {
+            self.vault_state.add_token_account()?;
+        }
+
+        let cpi_accounts = TransferChecked {
+            from: self.signer_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault_ata.to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer_checked(cpi_ctx, 1, self.mint.decimals)?;
+        Ok(())
+    }
+
+    pub fn withdraw_nft(&mut self) -> Result<()> {
+        self.vault_state.check_unlocked()?;
+        self.vault_state.remove_token_account();
+
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.vault_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.signer_ata.to_account_info(),
+            authority: self.vault.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        transfer_checked(cpi_ctx, 1, self.mint.decimals)?;
+
+        // The vault ATA is empty once the NFT leaves, return its rent to the signer
+        let cpi_accounts = CloseAccount {
+            account: self.vault_ata.to_account_info(),
+            destination: self.signer.to_account_info(),
+            authority: self.vault.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        close_account(cpi_ctx)?;
+        Ok(())
+    }
+}
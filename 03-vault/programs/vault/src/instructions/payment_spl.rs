@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{error::VaultError, state::VaultState};
+
+#[derive(Accounts)]
+pub struct PaymentSpl<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        seeds = [b"vault", vault_state.key().as_ref()],
+        bump = vault_state.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PaymentSpl<'info> {
+    pub fn deposit_spl(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        if self.vault_ata.amount == 0 {
+            self.vault_state.add_token_account()?;
+        }
+
+        let cpi_accounts = TransferChecked {
+            from: self.signer_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault_ata.to_account_info(),
+            authority: self.signer.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        Ok(())
+    }
+
+    pub fn withdraw_spl(&mut self, amount: u64) -> Result<()> {
+        self.vault_state.check_unlocked()?;
+        require!(amount > 0, VaultError::InvalidAmount);
+        let emptied = amount == self.vault_ata.amount;
+        if emptied {
+            self.vault_state.remove_token_account();
+        }
+
+        let vault_state_key = self.vault_state.to_account_info().key();
+        let seeds = &[
+            b"vault".as_ref(),
+            vault_state_key.as_ref(),
+            &[self.vault_state.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = TransferChecked {
+            from: self.vault_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.signer_ata.to_account_info(),
+            authority: self.vault.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+
+        // A full withdrawal leaves the vault ATA empty, return its rent to the signer
+        if emptied {
+            let cpi_accounts = CloseAccount {
+                account: self.vault_ata.to_account_info(),
+                destination: self.signer.to_account_info(),
+                authority: self.vault.to_account_info(),
+            };
+            let cpi_program = self.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+            close_account(cpi_ctx)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{MultisigVault, Proposal},
+};
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.creator.as_ref(), multisig.seed.to_le_bytes().as_ref()],
+        bump = multisig.multisig_bump,
+        constraint = multisig.is_owner(&proposer.key()) @ VaultError::NotAnOwner
+    )]
+    pub multisig: Account<'info, MultisigVault>,
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [b"proposal", multisig.key().as_ref(), multisig.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        space = Proposal::INIT_SPACE
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeWithdrawal<'info> {
+    pub fn propose(
+        &mut self,
+        recipient: Pubkey,
+        amount: u64,
+        bumps: &ProposeWithdrawalBumps,
+    ) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        // The proposer approves their own proposal
+        self.proposal.set_inner(Proposal {
+            multisig: self.multisig.key(),
+            index: self.multisig.proposal_count,
+            proposer: self.proposer.key(),
+            recipient,
+            amount,
+            approvals: vec![self.proposer.key()],
+            bump: bumps.proposal,
+        });
+
+        self.multisig.proposal_count = self
+            .multisig
+            .proposal_count
+            .checked_add(1)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{Delegation, VaultState},
+};
+
+#[derive(Accounts)]
+pub struct RevokeAllowance<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"delegation", vault_state.key().as_ref(), delegation.delegate.as_ref()],
+        bump = delegation.bump,
+        has_one = vault_state,
+        close = signer // Closes the delegation and returns its rent to the owner
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+impl<'info> RevokeAllowance<'info> {
+    pub fn revoke(&mut self) -> Result<()> {
+        self.vault_state.delegations = self
+            .vault_state
+            .delegations
+            .checked_sub(1)
+            .ok_or(VaultError::Underflow)?;
+        Ok(())
+    }
+}
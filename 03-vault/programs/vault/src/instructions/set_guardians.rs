@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::VaultError,
+    state::{GuardianSet, VaultState, MAX_GUARDIANS},
+};
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        seeds = [b"guardians", vault_state.key().as_ref()],
+        bump,
+        space = GuardianSet::INIT_SPACE
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+    /// CHECK: The vault's recovery PDA, guardians can't be swapped while it exists
+    #[account(
+        seeds = [b"recovery", vault_state.key().as_ref()],
+        bump,
+        constraint = recovery.owner != &crate::ID @ VaultError::RecoveryPending
+    )]
+    pub recovery: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetGuardians<'info> {
+    pub fn set_guardians(
+        &mut self,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        delay: i64,
+        bumps: &SetGuardiansBumps,
+    ) -> Result<()> {
+        require!(
+            !guardians.is_empty() && guardians.len() <= MAX_GUARDIANS,
+            VaultError::InvalidGuardians
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                !guardians[..i].contains(guardian) && *guardian != self.signer.key(),
+                VaultError::InvalidGuardians
+            );
+        }
+        require!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            VaultError::InvalidThreshold
+        );
+        require!(delay >= 0, VaultError::InvalidRecoveryDelay);
+
+        self.guardian_set.set_inner(GuardianSet {
+            vault_state: self.vault_state.key(),
+            guardians,
+            threshold,
+            delay,
+            bump: bumps.guardian_set,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Recovery, VaultState};
+
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub initiator: SystemAccount<'info>,
+    #[account(
+        seeds = [b"state", signer.key().as_ref()],
+        bump = vault_state.state_bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    #[account(
+        mut,
+        seeds = [b"recovery", vault_state.key().as_ref()],
+        bump = recovery.bump,
+        has_one = vault_state,
+        has_one = initiator,
+        close = initiator // Cancels the recovery and refunds the initiating guardian
+    )]
+    pub recovery: Account<'info, Recovery>,
+}
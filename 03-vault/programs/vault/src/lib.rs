@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
+
+pub mod error;
+pub mod events;
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod state;
+pub use state::*;
 
 declare_id!("HBWLD6rdtjA6jXbExWPXeaJQxmu7yirQAiZfke8NbDwE");
 
@@ -22,143 +30,158 @@ pub mod my_program {
         Ok(())
     }
 
-    pub fn close(ctx: Context<Close>) -> Result<()> {
-        ctx.accounts.close()?;
+    pub fn deposit_for(ctx: Context<DepositFor>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_for(amount)?;
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
-    #[account(
-        init,
-        payer = signer,
-        seeds = [b"state", signer.key().as_ref()],
-        bump,
-        space = VaultState::INIT_SPACE
-    )]
-    pub vault_state: Account<'info, VaultState>,
-    #[account(
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump
-    )]
-    pub vault: SystemAccount<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn deposit_spl(ctx: Context<PaymentSpl>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_spl(amount)?;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Payment<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump
-    )]
-    pub vault_state: Account<'info, VaultState>,
-    #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump = vault_state.vault_bump
-    )]
-    pub vault: SystemAccount<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn withdraw_spl(ctx: Context<PaymentSpl>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_spl(amount)?;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Close<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"state", signer.key().as_ref()],
-        bump = vault_state.state_bump,
-        close = signer // Closes vault_state and sends lamports to signer
-    )]
-    pub vault_state: Account<'info, VaultState>,
-    #[account(
-        mut,
-        seeds = [b"vault", vault_state.key().as_ref()],
-        bump = vault_state.vault_bump
-    )]
-    pub vault: SystemAccount<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn deposit_nft(ctx: Context<PaymentNft>) -> Result<()> {
+        ctx.accounts.deposit_nft()?;
+        Ok(())
+    }
 
-#[account]
-pub struct VaultState {
-    pub vault_bump: u8,
-    pub state_bump: u8,
-}
+    pub fn withdraw_nft(ctx: Context<PaymentNft>) -> Result<()> {
+        ctx.accounts.withdraw_nft()?;
+        Ok(())
+    }
 
-impl VaultState {
-    pub const INIT_SPACE: usize = 8 + 1 + 1; // Anchor discriminator (8) + vault_bump (1) + state_bump (1)
-}
+    pub fn lock(ctx: Context<Lock>, unlock_at: i64) -> Result<()> {
+        ctx.accounts.lock(unlock_at)?;
+        Ok(())
+    }
 
-impl<'info> Initialize<'info> {
-    pub fn initialise(&mut self, bumps: &InitializeBumps) -> Result<()> {
-        self.vault_state.vault_bump = bumps.vault;
-        self.vault_state.state_bump = bumps.vault_state;
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        seed: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_multisig(seed, owners, threshold, &ctx.bumps)?;
         Ok(())
     }
-}
 
-impl<'info> Payment<'info> {
-    pub fn deposit(&mut self, amount: u64) -> Result<()> {
-        let cpi_accounts = Transfer {
-            from: self.signer.to_account_info(),
-            to: self.vault.to_account_info(),
-        };
-        let cpi_program = self.system_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        transfer(cpi_ctx, amount)?;
-        Ok(())
-    }
-
-    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
-        let vault_state_key = self.vault_state.to_account_info().key();
-        let seeds = &[
-            b"vault".as_ref(),
-            vault_state_key.as_ref(),
-            &[self.vault_state.vault_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        let cpi_accounts = Transfer {
-            from: self.vault.to_account_info(),
-            to: self.signer.to_account_info(),
-        };
-        let cpi_program = self.system_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
-        transfer(cpi_ctx, amount)?;
+    pub fn deposit_multisig(ctx: Context<DepositMultisig>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount)?;
+        Ok(())
+    }
+
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.propose(recipient, amount, &ctx.bumps)?;
         Ok(())
     }
-}
 
-impl<'info> Close<'info> {
-    pub fn close(&mut self) -> Result<()> {
-        // Transfer remaining lamports from vault to signer
-        let vault_state_key = self.vault_state.to_account_info().key();
-        let vault_seeds = &[
-            b"vault".as_ref(),
-            vault_state_key.as_ref(),
-            &[self.vault_state.vault_bump],
-        ];
-        let signer_seeds = &[&vault_seeds[..]];
-
-        let lamports = self.vault.lamports();
-        if lamports > 0 {
-            let cpi_accounts = Transfer {
-                from: self.vault.to_account_info(),
-                to: self.signer.to_account_info(),
-            };
-            let cpi_program = self.system_program.to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
-            transfer(cpi_ctx, lamports)?;
-        }
-
-        // The vault_state account is closed automatically via the `close = signer` constraint
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        ctx.accounts.approve()?;
+        Ok(())
+    }
+
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        ctx.accounts.execute()?;
+        Ok(())
+    }
+
+    pub fn grant_allowance(
+        ctx: Context<GrantAllowance>,
+        delegate: Pubkey,
+        allowance: u64,
+        period: AllowancePeriod,
+    ) -> Result<()> {
+        ctx.accounts
+            .grant(delegate, allowance, period, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn revoke_allowance(ctx: Context<RevokeAllowance>) -> Result<()> {
+        // The delegation account is closed via the `close = signer` constraint
+        ctx.accounts.revoke()?;
+        Ok(())
+    }
+
+    pub fn delegated_withdraw(ctx: Context<DelegatedWithdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount)?;
+        Ok(())
+    }
+
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        recipient: Pubkey,
+        id: u64,
+        rate: u64,
+        end_time: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_stream(recipient, id, rate, end_time, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn claim(ctx: Context<ClaimStream>) -> Result<()> {
+        ctx.accounts.claim()?;
+        Ok(())
+    }
+
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        ctx.accounts.cancel()?;
+        Ok(())
+    }
+
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        delay: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_guardians(guardians, threshold, delay, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>, new_owner: Pubkey) -> Result<()> {
+        ctx.accounts.initiate(new_owner, &ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        ctx.accounts.approve()?;
+        Ok(())
+    }
+
+    pub fn veto_recovery(_ctx: Context<VetoRecovery>) -> Result<()> {
+        // The recovery account is closed via the `close = initiator` constraint
+        Ok(())
+    }
+
+    // Remaining accounts are the old vault's delegations, then (mint, old_vault_ata, new_vault_ata)
+    // triplets for every token the vault holds
+    pub fn execute_recovery<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteRecovery<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.migrate(&ctx.bumps)?;
+        let token_accounts = ctx.accounts.close_delegations(ctx.remaining_accounts)?;
+        ctx.accounts.migrate_token_accounts(token_accounts)?;
+        Ok(())
+    }
+
+    // Remaining accounts are the vault's delegations, then (mint, vault_ata, signer_ata) triplets
+    // for every token the vault holds
+    pub fn close<'info>(ctx: Context<'_, '_, 'info, 'info, Close<'info>>) -> Result<()> {
+        ctx.accounts.close()?;
+        let token_accounts = ctx.accounts.close_delegations(ctx.remaining_accounts)?;
+        ctx.accounts.sweep_token_accounts(token_accounts)?;
         Ok(())
     }
 }
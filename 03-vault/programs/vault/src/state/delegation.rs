@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::VaultError, state::VaultState};
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AllowancePeriod {
+    Epoch,
+    Day,
+}
+
+impl AllowancePeriod {
+    pub fn current(&self, clock: &Clock) -> u64 {
+        match self {
+            AllowancePeriod::Epoch => clock.epoch,
+            AllowancePeriod::Day => (clock.unix_timestamp / SECONDS_PER_DAY) as u64,
+        }
+    }
+}
+
+#[account]
+pub struct Delegation {
+    pub vault_state: Pubkey,
+    pub delegate: Pubkey,
+    pub allowance: u64,
+    pub period: AllowancePeriod,
+    pub current_period: u64,
+    pub spent: u64,
+    pub bump: u8,
+}
+
+impl Delegation {
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8 + 1; // Anchor discriminator (8) + vault_state (32) + delegate (32) + allowance (8) + period (1) + current_period (8) + spent (8) + bump (1)
+
+    // Records spending against the allowance, resetting it when a new period starts
+    pub fn spend(&mut self, amount: u64) -> Result<()> {
+        let period = self.period.current(&Clock::get()?);
+        if period != self.current_period {
+            self.current_period = period;
+            self.spent = 0;
+        }
+
+        let spent = self.spent.checked_add(amount).ok_or(VaultError::Overflow)?;
+        require!(spent <= self.allowance, VaultError::AllowanceExceeded);
+
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+impl Delegation {
+    // Closes the vault's delegations at the start of `accounts` and returns the accounts after them
+    pub fn close_all<'info>(
+        vault_state: &Account<'info, VaultState>,
+        accounts: &'info [AccountInfo<'info>],
+        destination: &AccountInfo<'info>,
+    ) -> Result<&'info [AccountInfo<'info>]> {
+        let count = vault_state.delegations as usize;
+        require!(accounts.len() >= count, VaultError::MissingDelegations);
+        let (delegations, rest) = accounts.split_at(count);
+
+        for info in delegations {
+            // A delegation closed earlier in this loop no longer deserializes, so duplicates fail
+            let delegation = Account::<Delegation>::try_from(info)
+                .map_err(|_| VaultError::MissingDelegations)?;
+            require_keys_eq!(
+                delegation.vault_state,
+                vault_state.key(),
+                VaultError::MissingDelegations
+            );
+            delegation.close(destination.clone())?;
+        }
+
+        Ok(rest)
+    }
+}
@@ -0,0 +1,14 @@
+pub mod vault_state;
+pub use vault_state::*;
+
+pub mod multisig;
+pub use multisig::*;
+
+pub mod delegation;
+pub use delegation::*;
+
+pub mod stream;
+pub use stream::*;
+
+pub mod recovery;
+pub use recovery::*;
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_OWNERS: usize = 10;
+
+#[account]
+pub struct MultisigVault {
+    pub creator: Pubkey,
+    pub seed: u64,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub multisig_bump: u8,
+    pub vault_bump: u8,
+}
+
+impl MultisigVault {
+    pub const INIT_SPACE: usize = 8 + 32 + 8 + (4 + 32 * MAX_OWNERS) + 1 + 8 + 1 + 1; // Anchor discriminator (8) + creator (32) + seed (8) + owners (4 + 32 * MAX_OWNERS) + threshold (1) + proposal_count (8) + multisig_bump (1) + vault_bump (1)
+
+    pub fn is_owner(&self, key: &Pubkey) -> bool {
+        self.owners.contains(key)
+    }
+}
+
+#[account]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub approvals: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const INIT_SPACE: usize = 8 + 32 + 8 + 32 + 32 + 8 + (4 + 32 * MAX_OWNERS) + 1; // Anchor discriminator (8) + multisig (32) + index (8) + proposer (32) + recipient (32) + amount (8) + approvals (4 + 32 * MAX_OWNERS) + bump (1)
+}
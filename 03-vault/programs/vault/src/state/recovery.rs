@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+
+pub const MAX_GUARDIANS: usize = 10;
+
+#[account]
+pub struct GuardianSet {
+    pub vault_state: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub delay: i64,
+    pub bump: u8,
+}
+
+impl GuardianSet {
+    pub const INIT_SPACE: usize = 8 + 32 + (4 + 32 * MAX_GUARDIANS) + 1 + 8 + 1; // Anchor discriminator (8) + vault_state (32) + guardians (4 + 32 * MAX_GUARDIANS) + threshold (1) + delay (8) + bump (1)
+
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians.contains(key)
+    }
+}
+
+#[account]
+pub struct Recovery {
+    pub vault_state: Pubkey,
+    pub initiator: Pubkey,
+    pub new_owner: Pubkey,
+    pub approvals: Vec<Pubkey>,
+    pub ready_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl Recovery {
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 32 + (4 + 32 * MAX_GUARDIANS) + (1 + 8) + 1; // Anchor discriminator (8) + vault_state (32) + initiator (32) + new_owner (32) + approvals (4 + 32 * MAX_GUARDIANS) + ready_at (1 + 8) + bump (1)
+
+    // The timelock starts once the guardian threshold is reached
+    pub fn approve(&mut self, guardian: Pubkey, guardian_set: &GuardianSet) -> Result<()> {
+        self.approvals.push(guardian);
+        if self.ready_at.is_none() && self.approvals.len() >= guardian_set.threshold as usize {
+            let ready_at = Clock::get()?
+                .unix_timestamp
+                .checked_add(guardian_set.delay)
+                .ok_or(VaultError::Overflow)?;
+            self.ready_at = Some(ready_at);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Stream {
+    pub vault_state: Pubkey,
+    pub recipient: Pubkey,
+    pub id: u64,
+    pub rate: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total: u64,
+    pub withdrawn: u64,
+    pub bump: u8,
+}
+
+impl Stream {
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1; // Anchor discriminator (8) + vault_state (32) + recipient (32) + id (8) + rate (8) + start_time (8) + end_time (8) + total (8) + withdrawn (8) + bump (1)
+
+    // Lamports vested at `now`, paid at `rate` per second from start_time until end_time
+    pub fn vested_at(&self, now: i64) -> u64 {
+        if now >= self.end_time {
+            return self.total;
+        }
+        let elapsed = now.saturating_sub(self.start_time).max(0) as u64;
+        self.rate.saturating_mul(elapsed).min(self.total)
+    }
+
+    pub fn claimable_at(&self, now: i64) -> u64 {
+        self.vested_at(now) - self.withdrawn
+    }
+}
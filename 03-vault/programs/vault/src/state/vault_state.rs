@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+
+#[account]
+pub struct VaultState {
+    pub vault_bump: u8,
+    pub state_bump: u8,
+    pub unlock_at: Option<i64>,
+    pub balance: u64,
+    pub streamed: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub delegations: u32,
+    pub token_accounts: u32,
+}
+
+impl VaultState {
+    pub const INIT_SPACE: usize = 8 + 1 + 1 + (1 + 8) + 8 + 8 + 8 + 8 + 4 + 4; // Anchor discriminator (8) + vault_bump (1) + state_bump (1) + unlock_at (1 + 8) + balance (8) + streamed (8) + total_deposited (8) + total_withdrawn (8) + delegations (4) + token_accounts (4)
+
+    pub fn check_unlocked(&self) -> Result<()> {
+        if let Some(unlock_at) = self.unlock_at {
+            require!(
+                Clock::get()?.unix_timestamp >= unlock_at,
+                VaultError::VaultLocked
+            );
+        }
+        Ok(())
+    }
+
+    // Checks a deposit leaves the vault rent exempt
+    pub fn check_deposit(vault_lamports: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let vault_lamports = vault_lamports
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        require!(
+            vault_lamports >= Rent::get()?.minimum_balance(0),
+            VaultError::DepositBelowRentExemption
+        );
+        Ok(())
+    }
+
+    // Checks a withdrawal empties the vault or leaves it rent exempt
+    pub fn check_withdrawal(vault_lamports: u64, amount: u64) -> Result<()> {
+        require!(amount <= vault_lamports, VaultError::InsufficientFunds);
+        let remaining = vault_lamports - amount;
+        require!(
+            remaining == 0 || remaining >= Rent::get()?.minimum_balance(0),
+            VaultError::WithdrawalLeavesDust
+        );
+        Ok(())
+    }
+
+    // Checks a deposit keeps the vault rent exempt and credits it to the balance
+    pub fn credit(&mut self, vault_lamports: u64, amount: u64) -> Result<()> {
+        Self::check_deposit(vault_lamports, amount)?;
+
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    // Checks a withdrawal against the tracked balance and the rent-exempt minimum of the vault
+    pub fn debit(&mut self, vault_lamports: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(amount <= self.balance, VaultError::InsufficientFunds);
+        Self::check_withdrawal(vault_lamports, amount)?;
+        if self.streamed > 0 {
+            require!(
+                self.balance - amount >= Rent::get()?.minimum_balance(0),
+                VaultError::StreamRentReserved
+            );
+        }
+
+        self.balance -= amount;
+        self.record_withdrawal(amount)
+    }
+
+    // Counts the vault token accounts holding tokens, close and recovery must move all of them
+    pub fn add_token_account(&mut self) -> Result<()> {
+        self.token_accounts = self
+            .token_accounts
+            .checked_add(1)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn remove_token_account(&mut self) {
+        self.token_accounts = self.token_accounts.saturating_sub(1);
+    }
+
+    fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.total_withdrawn = self
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    // Pays out lamports reserved for streams. There is no dust check, the balance keeps the
+    // vault rent exempt while streams are open so recipients can always claim.
+    pub fn release_stream(&mut self, vault_lamports: u64, amount: u64) -> Result<()> {
+        require!(amount <= vault_lamports, VaultError::InsufficientFunds);
+
+        self.streamed = self
+            .streamed
+            .checked_sub(amount)
+            .ok_or(VaultError::Underflow)?;
+        self.record_withdrawal(amount)
+    }
+}
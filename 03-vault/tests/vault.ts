@@ -6,6 +6,18 @@ import {
   SystemProgram,
   LAMPORTS_PER_SOL,
 } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  PROGRAM_ID as METADATA_PROGRAM_ID,
+  createCreateMetadataAccountV3Instruction,
+  createCreateMasterEditionV3Instruction,
+} from "@metaplex-foundation/mpl-token-metadata";
 import { expect } from "chai";
 
 describe("vault", () => {
@@ -49,6 +61,18 @@ describe("vault", () => {
     expect(vaultInfo).to.not.be.null;
   });
 
+  it("Rejects deposits that leave the vault below rent exemption", async () => {
+    try {
+      await program.methods
+        .deposit(new anchor.BN(1000))
+        .accounts(baseAccounts)
+        .rpc();
+      expect.fail("Deposit below rent exemption should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("DepositBelowRentExemption");
+    }
+  });
+
   it("Deposits lamports into the vault", async () => {
     const depositAmount = 1 * LAMPORTS_PER_SOL; // 1 SOL
     const signerBalanceBefore = await provider.connection.getBalance(signer.publicKey);
@@ -101,40 +125,1016 @@ describe("vault", () => {
     );
   });
 
-  it("Closes the vault state and vault", async () => {
-    const signerBalanceBefore = await provider.connection.getBalance(signer.publicKey);
+  it("Lets anyone deposit into the signer's vault", async () => {
+    const payer = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      payer.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
     const vaultBalanceBefore = await provider.connection.getBalance(vaultPda);
-    const vaultStateBalanceBefore = await provider.connection.getBalance(vaultStatePda);
 
-    const tx = await program.methods
-      .close()
-      .accounts(baseAccounts)
+    await program.methods
+      .depositFor(new anchor.BN(0.1 * LAMPORTS_PER_SOL))
+      .accounts({ payer: payer.publicKey, owner: signer.publicKey })
+      .signers([payer])
       .rpc();
-    console.log("Close tx signature:", tx);
 
-    // Verify vault_state is closed
-    const vaultStateInfo = await provider.connection.getAccountInfo(vaultStatePda);
-    expect(vaultStateInfo).to.be.null;
+    const vaultBalanceAfter = await provider.connection.getBalance(vaultPda);
+    expect(vaultBalanceAfter - vaultBalanceBefore).to.equal(
+      0.1 * LAMPORTS_PER_SOL
+    );
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.balance.toNumber()).to.equal(0.6 * LAMPORTS_PER_SOL);
+  });
 
-    // Verify vault lamports transferred to signer
-    const signerBalanceAfter = await provider.connection.getBalance(signer.publicKey);
-    const vaultInfoAfterClose = await provider.connection.getAccountInfo(vaultPda);
+  it("Tracks cumulative deposits and withdrawals", async () => {
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.totalDeposited.toNumber()).to.equal(
+      (11 * LAMPORTS_PER_SOL) / 10
+    );
+    expect(vaultState.totalWithdrawn.toNumber()).to.equal(
+      0.5 * LAMPORTS_PER_SOL
+    );
+  });
+
+  const delegate = anchor.web3.Keypair.generate();
+  const [delegation] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("delegation"),
+      vaultStatePda.toBuffer(),
+      delegate.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
 
-    // The vault account itself isn't closed, only the vault_state account is.
-    // Lamports should be transferred though.
-    expect(vaultInfoAfterClose?.lamports).to.equal(
-      0,
-      "Vault lamports should be 0 after transfer"
+  it("Grants a daily allowance to a delegate", async () => {
+    await program.methods
+      .grantAllowance(
+        delegate.publicKey,
+        new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+        { day: {} }
+      )
+      .accounts({ signer: signer.publicKey })
+      .rpc();
+
+    const delegationAccount = await program.account.delegation.fetch(
+      delegation
+    );
+    expect(delegationAccount.allowance.toNumber()).to.equal(
+      0.1 * LAMPORTS_PER_SOL
     );
+  });
 
-    // Signer should receive lamports from vault_state closure and vault transfer, minus fee
-    const expectedGain = vaultBalanceBefore + vaultStateBalanceBefore - 5000; // Approx fee
-    expect(signerBalanceAfter - signerBalanceBefore).to.be.at.least(
-        expectedGain,
-        "Signer should receive vault and vault_state lamports (minus fees)"
+  it("Lets the delegate spend within the allowance", async () => {
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+
+    await program.methods
+      .delegatedWithdraw(new anchor.BN(0.06 * LAMPORTS_PER_SOL))
+      .accounts({
+        delegate: delegate.publicKey,
+        owner: signer.publicKey,
+        recipient,
+      })
+      .signers([delegate])
+      .rpc();
+
+    expect(await provider.connection.getBalance(recipient)).to.equal(
+      0.06 * LAMPORTS_PER_SOL
+    );
+  });
+
+  it("Rejects delegate spending above the allowance", async () => {
+    try {
+      await program.methods
+        .delegatedWithdraw(new anchor.BN(0.06 * LAMPORTS_PER_SOL))
+        .accounts({
+          delegate: delegate.publicKey,
+          owner: signer.publicKey,
+          recipient: delegate.publicKey,
+        })
+        .signers([delegate])
+        .rpc();
+      expect.fail("Delegated withdraw above allowance should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("AllowanceExceeded");
+    }
+  });
+
+  it("Revokes the allowance", async () => {
+    await program.methods
+      .revokeAllowance()
+      .accounts({ signer: signer.publicKey, delegation })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(delegation)).to.be.null;
+  });
+
+  const streamRecipient = anchor.web3.Keypair.generate();
+  const streamPda = (id: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("stream"),
+        vaultStatePda.toBuffer(),
+        streamRecipient.publicKey.toBuffer(),
+        new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  const stream = streamPda(0);
+  const secondStream = streamPda(1);
+
+  it("Creates a payment stream out of the vault", async () => {
+    const sig = await provider.connection.requestAirdrop(
+      streamRecipient.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const endTime = Math.floor(Date.now() / 1000) + 10;
+    await program.methods
+      .createStream(
+        streamRecipient.publicKey,
+        new anchor.BN(0),
+        new anchor.BN(0.001 * LAMPORTS_PER_SOL),
+        new anchor.BN(endTime)
+      )
+      .accounts({ signer: signer.publicKey })
+      .rpc();
+
+    const streamAccount = await program.account.stream.fetch(stream);
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.streamed.toNumber()).to.equal(
+      streamAccount.total.toNumber()
+    );
+  });
+
+  it("Opens a second stream to the same recipient under another id", async () => {
+    const endTime = Math.floor(Date.now() / 1000) + 10;
+    await program.methods
+      .createStream(
+        streamRecipient.publicKey,
+        new anchor.BN(1),
+        new anchor.BN(0.001 * LAMPORTS_PER_SOL),
+        new anchor.BN(endTime)
+      )
+      .accounts({ signer: signer.publicKey })
+      .rpc();
+
+    const streamAccount = await program.account.stream.fetch(secondStream);
+    expect(streamAccount.id.toNumber()).to.equal(1);
+    expect(streamAccount.recipient.toBase58()).to.equal(
+      streamRecipient.publicKey.toBase58()
+    );
+  });
+
+  it("Keeps the rent exempt minimum in the balance while streams are open", async () => {
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(0);
+
+    try {
+      await program.methods
+        .withdraw(vaultState.balance.subn(rent - 1))
+        .accounts(baseAccounts)
+        .rpc();
+      expect.fail("Withdrawing the stream rent reserve should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("StreamRentReserved");
+    }
+  });
+
+  it("Lets the recipient claim vested lamports", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    const balanceBefore = await provider.connection.getBalance(
+      streamRecipient.publicKey
+    );
+
+    await program.methods
+      .claim()
+      .accounts({
+        recipient: streamRecipient.publicKey,
+        owner: signer.publicKey,
+        stream,
+      })
+      .signers([streamRecipient])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(
+      streamRecipient.publicKey
+    );
+    expect(balanceAfter).to.be.greaterThan(balanceBefore);
+  });
+
+  it("Cancels the streams and reclaims the unvested remainder", async () => {
+    for (const pda of [stream, secondStream]) {
+      await program.methods
+        .cancelStream()
+        .accounts({
+          signer: signer.publicKey,
+          recipient: streamRecipient.publicKey,
+          stream: pda,
+        })
+        .rpc();
+    }
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.streamed.toNumber()).to.equal(0);
+    expect(await provider.connection.getAccountInfo(stream)).to.be.null;
+    expect(await provider.connection.getAccountInfo(secondStream)).to.be.null;
+  });
+
+  // (mint, vault_ata, signer_ata) triplets swept on close
+  const tokenAccounts: anchor.web3.AccountMeta[] = [];
+
+  describe("SPL tokens", () => {
+    let mint: PublicKey;
+    let signerAta: PublicKey;
+    let vaultAta: PublicKey;
+
+    before(async () => {
+      mint = await createMint(
+        provider.connection,
+        signer.payer,
+        signer.publicKey,
+        null,
+        6
+      );
+      signerAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          signer.payer,
+          mint,
+          signer.publicKey
+        )
+      ).address;
+      vaultAta = getAssociatedTokenAddressSync(mint, vaultPda, true);
+      await mintTo(
+        provider.connection,
+        signer.payer,
+        mint,
+        signerAta,
+        signer.payer,
+        1_000_000
+      );
+    });
+
+    it("Deposits tokens into the vault ATA", async () => {
+      const tx = await program.methods
+        .depositSpl(new anchor.BN(600_000))
+        .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      console.log("Deposit SPL tx signature:", tx);
+
+      const vaultBalance = await provider.connection.getTokenAccountBalance(
+        vaultAta
+      );
+      expect(vaultBalance.value.amount).to.equal("600000");
+    });
+
+    it("Withdraws tokens from the vault ATA", async () => {
+      const tx = await program.methods
+        .withdrawSpl(new anchor.BN(100_000))
+        .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      console.log("Withdraw SPL tx signature:", tx);
+
+      const vaultBalance = await provider.connection.getTokenAccountBalance(
+        vaultAta
+      );
+      expect(vaultBalance.value.amount).to.equal("500000");
+    });
+
+    it("Closes the vault ATA on a full withdrawal", async () => {
+      await program.methods
+        .withdrawSpl(new anchor.BN(500_000))
+        .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      expect(await provider.connection.getAccountInfo(vaultAta)).to.be.null;
+
+      // Deposit again so the close test below has a token account to sweep
+      await program.methods
+        .depositSpl(new anchor.BN(500_000))
+        .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      const vaultBalance = await provider.connection.getTokenAccountBalance(
+        vaultAta
+      );
+      expect(vaultBalance.value.amount).to.equal("500000");
+    });
+
+    it("Rejects depositing a token without a master edition as an NFT", async () => {
+      try {
+        await program.methods
+          .depositNft()
+          .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+          .rpc();
+        expect.fail("Deposit NFT should fail without metadata");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("AccountNotInitialized");
+      }
+    });
+
+    after(async () => {
+      // Sweep the vault ATA as part of the close test below
+      tokenAccounts.push(
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: vaultAta, isSigner: false, isWritable: true },
+        { pubkey: signerAta, isSigner: false, isWritable: true }
+      );
+    });
+  });
+
+  describe("NFTs", () => {
+    let mint: PublicKey;
+    let vaultAta: PublicKey;
+
+    const metadataPda = (mint: PublicKey, ...extra: Buffer[]) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          METADATA_PROGRAM_ID.toBuffer(),
+          mint.toBuffer(),
+          ...extra,
+        ],
+        METADATA_PROGRAM_ID
+      )[0];
+
+    before(async () => {
+      mint = await createMint(
+        provider.connection,
+        signer.payer,
+        signer.publicKey,
+        signer.publicKey,
+        0
+      );
+      const signerAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          signer.payer,
+          mint,
+          signer.publicKey
+        )
+      ).address;
+      vaultAta = getAssociatedTokenAddressSync(mint, vaultPda, true);
+      await mintTo(
+        provider.connection,
+        signer.payer,
+        mint,
+        signerAta,
+        signer.payer,
+        1
+      );
+
+      const tx = new anchor.web3.Transaction().add(
+        createCreateMetadataAccountV3Instruction(
+          {
+            metadata: metadataPda(mint),
+            mint,
+            mintAuthority: signer.publicKey,
+            payer: signer.publicKey,
+            updateAuthority: signer.publicKey,
+          },
+          {
+            createMetadataAccountArgsV3: {
+              data: {
+                name: "Vault NFT",
+                symbol: "VLT",
+                uri: "https://test.uri/vault.json",
+                sellerFeeBasisPoints: 0,
+                creators: null,
+                collection: null,
+                uses: null,
+              },
+              isMutable: true,
+              collectionDetails: null,
+            },
+          }
+        ),
+        createCreateMasterEditionV3Instruction(
+          {
+            edition: metadataPda(mint, Buffer.from("edition")),
+            mint,
+            updateAuthority: signer.publicKey,
+            mintAuthority: signer.publicKey,
+            payer: signer.publicKey,
+            metadata: metadataPda(mint),
+          },
+          { createMasterEditionArgs: { maxSupply: 0 } }
+        )
+      );
+      await provider.sendAndConfirm(tx);
+    });
+
+    it("Deposits a master edition NFT into the vault ATA", async () => {
+      const { tokenAccounts: countBefore } =
+        await program.account.vaultState.fetch(vaultStatePda);
+
+      const tx = await program.methods
+        .depositNft()
+        .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      console.log("Deposit NFT tx signature:", tx);
+
+      const vaultBalance = await provider.connection.getTokenAccountBalance(
+        vaultAta
+      );
+      expect(vaultBalance.value.amount).to.equal("1");
+      const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+      expect(vaultState.tokenAccounts).to.equal(countBefore + 1);
+    });
+
+    it("Withdraws the NFT and closes the vault ATA", async () => {
+      const { tokenAccounts: countBefore } =
+        await program.account.vaultState.fetch(vaultStatePda);
+
+      const tx = await program.methods
+        .withdrawNft()
+        .accounts({ mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      console.log("Withdraw NFT tx signature:", tx);
+
+      expect(await provider.connection.getAccountInfo(vaultAta)).to.be.null;
+      const signerBalance = await provider.connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(mint, signer.publicKey)
+      );
+      expect(signerBalance.value.amount).to.equal("1");
+      const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+      expect(vaultState.tokenAccounts).to.equal(countBefore - 1);
+    });
+  });
+
+  // Runs after the token suites, mocha runs a suite's own tests before its child suites
+  describe("close", () => {
+    const delegationMeta = {
+      pubkey: delegation,
+      isSigner: false,
+      isWritable: true,
+    };
+
+    it("Rejects closing without the vault's delegations", async () => {
+      await program.methods
+        .grantAllowance(
+          delegate.publicKey,
+          new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+          { day: {} }
+        )
+        .accounts({ signer: signer.publicKey })
+        .rpc();
+
+      try {
+        await program.methods
+          .close()
+          .accounts({ ...baseAccounts, tokenProgram: TOKEN_PROGRAM_ID })
+          .remainingAccounts(tokenAccounts)
+          .rpc();
+        expect.fail("Close should fail while a delegation is left out");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("MissingDelegations");
+      }
+    });
+
+    const [guardianSet] = PublicKey.findProgramAddressSync(
+      [Buffer.from("guardians"), vaultStatePda.toBuffer()],
+      program.programId
+    );
+
+    it("Rejects closing while a recovery is pending", async () => {
+      const guardian = anchor.web3.Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        guardian.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      await program.methods
+        .setGuardians([guardian.publicKey], 1, new anchor.BN(0))
+        .accounts({ signer: signer.publicKey })
+        .rpc();
+      await program.methods
+        .initiateRecovery(anchor.web3.Keypair.generate().publicKey)
+        .accounts({ guardian: guardian.publicKey, guardianSet })
+        .signers([guardian])
+        .rpc();
+
+      try {
+        await program.methods
+          .close()
+          .accounts({ ...baseAccounts, tokenProgram: TOKEN_PROGRAM_ID })
+          .remainingAccounts([delegationMeta, ...tokenAccounts])
+          .rpc();
+        expect.fail("Close should fail while a recovery is pending");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("RecoveryPending");
+      }
+
+      await program.methods
+        .vetoRecovery()
+        .accounts({ signer: signer.publicKey, initiator: guardian.publicKey })
+        .rpc();
+    });
+
+    it("Rejects closing without every vault token account", async () => {
+      try {
+        await program.methods
+          .close()
+          .accounts({ ...baseAccounts, tokenProgram: TOKEN_PROGRAM_ID })
+          .remainingAccounts([delegationMeta])
+          .rpc();
+        expect.fail("Close should fail while a token account is left out");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("MissingTokenAccounts");
+      }
+    });
+
+    it("Closes the vault state and vault", async () => {
+      const signerBalanceBefore = await provider.connection.getBalance(signer.publicKey);
+      const vaultBalanceBefore = await provider.connection.getBalance(vaultPda);
+      const vaultStateBalanceBefore = await provider.connection.getBalance(vaultStatePda);
+
+      const tx = await program.methods
+        .close()
+        .accounts({ ...baseAccounts, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts([delegationMeta, ...tokenAccounts])
+        .rpc();
+      console.log("Close tx signature:", tx);
+
+      // Verify vault_state is closed
+      const vaultStateInfo = await provider.connection.getAccountInfo(vaultStatePda);
+      expect(vaultStateInfo).to.be.null;
+
+      // Verify vault lamports transferred to signer
+      const signerBalanceAfter = await provider.connection.getBalance(signer.publicKey);
+      const vaultInfoAfterClose = await provider.connection.getAccountInfo(vaultPda);
+
+      // The vault account itself isn't closed, only the vault_state account is.
+      // Lamports should be transferred though.
+      expect(vaultInfoAfterClose?.lamports).to.equal(
+        0,
+        "Vault lamports should be 0 after transfer"
+      );
+
+      // Signer should receive lamports from vault_state closure and vault transfer, minus fee
+      const expectedGain = vaultBalanceBefore + vaultStateBalanceBefore - 5000; // Approx fee
+      expect(signerBalanceAfter - signerBalanceBefore).to.be.at.least(
+          expectedGain,
+          "Signer should receive vault and vault_state lamports (minus fees)"
+      );
+
+      // Verify the delegation and guardian set were closed with the vault
+      expect(await provider.connection.getAccountInfo(delegation)).to.be.null;
+      expect(await provider.connection.getAccountInfo(guardianSet)).to.be.null;
+
+      // Verify the vault token accounts were swept and closed
+      for (let i = 1; i < tokenAccounts.length; i += 3) {
+        const vaultAtaInfo = await provider.connection.getAccountInfo(
+          tokenAccounts[i].pubkey
+        );
+        expect(vaultAtaInfo).to.be.null;
+      }
+
+      // Log final state
+      console.log("Vault info after close:", vaultInfoAfterClose);
+    });
+  });
+
+  describe("time lock", () => {
+    const user = anchor.web3.Keypair.generate();
+
+    const [userVaultState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(
+        user.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      await program.methods
+        .initialize()
+        .accounts({ signer: user.publicKey })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .deposit(new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts({ signer: user.publicKey })
+        .signers([user])
+        .rpc();
+    });
+
+    it("Locks the vault until a future time", async () => {
+      const unlockAt = Math.floor(Date.now() / 1000) + 3600;
+
+      await program.methods
+        .lock(new anchor.BN(unlockAt))
+        .accounts({ signer: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      const vaultState = await program.account.vaultState.fetch(userVaultState);
+      expect(vaultState.unlockAt.toNumber()).to.equal(unlockAt);
+    });
+
+    it("Rejects shortening the lock", async () => {
+      try {
+        await program.methods
+          .lock(new anchor.BN(Math.floor(Date.now() / 1000) + 60))
+          .accounts({ signer: user.publicKey })
+          .signers([user])
+          .rpc();
+        expect.fail("Lock should not be shortened");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("InvalidUnlockTime");
+      }
+    });
+
+    it("Rejects withdrawals before the unlock time", async () => {
+      try {
+        await program.methods
+          .withdraw(new anchor.BN(LAMPORTS_PER_SOL / 2))
+          .accounts({ signer: user.publicKey })
+          .signers([user])
+          .rpc();
+        expect.fail("Withdraw should fail while locked");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("VaultLocked");
+      }
+    });
+
+    it("Rejects closing before the unlock time", async () => {
+      try {
+        await program.methods
+          .close()
+          .accounts({ signer: user.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+          .signers([user])
+          .rpc();
+        expect.fail("Close should fail while locked");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("VaultLocked");
+      }
+    });
+  });
+
+  describe("multisig", () => {
+    const [ownerA, ownerB, outsider, recipient] = Array.from(
+      { length: 4 },
+      () => anchor.web3.Keypair.generate()
     );
+    const seed = new anchor.BN(1);
+
+    const [multisig] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("multisig"),
+        signer.publicKey.toBuffer(),
+        seed.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [multisigVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), multisig.toBuffer()],
+      program.programId
+    );
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        multisig.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    before(async () => {
+      for (const owner of [ownerA, ownerB, outsider]) {
+        const sig = await provider.connection.requestAirdrop(
+          owner.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+    });
+
+    it("Rejects a threshold above the number of owners", async () => {
+      try {
+        await program.methods
+          .createMultisig(seed, [signer.publicKey, ownerA.publicKey], 3)
+          .accounts({ creator: signer.publicKey })
+          .rpc();
+        expect.fail("Create multisig should fail");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("InvalidThreshold");
+      }
+    });
+
+    it("Creates a 2-of-3 multisig vault and funds it", async () => {
+      await program.methods
+        .createMultisig(
+          seed,
+          [signer.publicKey, ownerA.publicKey, ownerB.publicKey],
+          2
+        )
+        .accounts({ creator: signer.publicKey })
+        .rpc();
+      await program.methods
+        .depositMultisig(new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts({ signer: signer.publicKey, multisig })
+        .rpc();
+
+      const vaultBalance = await provider.connection.getBalance(multisigVault);
+      expect(vaultBalance).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("Proposes a withdrawal", async () => {
+      await program.methods
+        .proposeWithdrawal(recipient.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 2))
+        .accounts({ proposer: ownerA.publicKey, multisig })
+        .signers([ownerA])
+        .rpc();
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      expect(proposalAccount.approvals).to.have.length(1);
+    });
+
+    it("Rejects execution below the threshold", async () => {
+      try {
+        await program.methods
+          .executeWithdrawal()
+          .accounts({
+            signer: signer.publicKey,
+            proposer: ownerA.publicKey,
+            recipient: recipient.publicKey,
+            multisig,
+            proposal,
+          })
+          .rpc();
+        expect.fail("Execute should fail below threshold");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("ThresholdNotMet");
+      }
+    });
+
+    it("Rejects approvals from non-owners and repeat approvals", async () => {
+      for (const [approver, code] of [
+        [outsider, "NotAnOwner"],
+        [ownerA, "AlreadyApproved"],
+      ] as const) {
+        try {
+          await program.methods
+            .approveWithdrawal()
+            .accounts({ owner: approver.publicKey, multisig, proposal })
+            .signers([approver])
+            .rpc();
+          expect.fail("Approve should fail");
+        } catch (e) {
+          expect(e.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
+
+    it("Executes the withdrawal once approved", async () => {
+      await program.methods
+        .approveWithdrawal()
+        .accounts({ owner: ownerB.publicKey, multisig, proposal })
+        .signers([ownerB])
+        .rpc();
+      await program.methods
+        .executeWithdrawal()
+        .accounts({
+          signer: signer.publicKey,
+          proposer: ownerA.publicKey,
+          recipient: recipient.publicKey,
+          multisig,
+          proposal,
+        })
+        .rpc();
+
+      const recipientBalance = await provider.connection.getBalance(
+        recipient.publicKey
+      );
+      expect(recipientBalance).to.equal(LAMPORTS_PER_SOL / 2);
+      expect(await provider.connection.getAccountInfo(proposal)).to.be.null;
+    });
+  });
+
+  describe("social recovery", () => {
+    const [owner, newOwner, guardianA, guardianB, outsider] = Array.from(
+      { length: 5 },
+      () => anchor.web3.Keypair.generate()
+    );
+
+    const [ownerVaultState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), owner.publicKey.toBuffer()],
+      program.programId
+    );
+    const [recovery] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recovery"), ownerVaultState.toBuffer()],
+      program.programId
+    );
+    const [guardianSet] = PublicKey.findProgramAddressSync(
+      [Buffer.from("guardians"), ownerVaultState.toBuffer()],
+      program.programId
+    );
+    const [newVaultState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("state"), newOwner.publicKey.toBuffer()],
+      program.programId
+    );
+    const [newVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), newVaultState.toBuffer()],
+      program.programId
+    );
+
+    const initiate = (guardian: anchor.web3.Keypair) =>
+      program.methods
+        .initiateRecovery(newOwner.publicKey)
+        .accounts({ guardian: guardian.publicKey, guardianSet })
+        .signers([guardian])
+        .rpc();
+
+    const approve = (guardian: anchor.web3.Keypair) =>
+      program.methods
+        .approveRecovery()
+        .accounts({ guardian: guardian.publicKey, guardianSet })
+        .signers([guardian])
+        .rpc();
+
+    const [ownerVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), ownerVaultState.toBuffer()],
+      program.programId
+    );
+    let mint: PublicKey;
+    let ownerVaultAta: PublicKey;
+    let newVaultAta: PublicKey;
+
+    const execute = (remainingAccounts: anchor.web3.AccountMeta[] = []) =>
+      program.methods
+        .executeRecovery()
+        .accounts({
+          newOwner: newOwner.publicKey,
+          oldOwner: owner.publicKey,
+          initiator: guardianA.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([newOwner])
+        .rpc();
+
+    before(async () => {
+      for (const account of [owner, newOwner, guardianA, guardianB]) {
+        const sig = await provider.connection.requestAirdrop(
+          account.publicKey,
+          2 * LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      await program.methods
+        .initialize()
+        .accounts({ signer: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await program.methods
+        .deposit(new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts({ signer: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await program.methods
+        .setGuardians([guardianA.publicKey, guardianB.publicKey], 2, new anchor.BN(0))
+        .accounts({ signer: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      // Give the vault a token account that has to move with it
+      mint = await createMint(
+        provider.connection,
+        signer.payer,
+        signer.publicKey,
+        null,
+        6
+      );
+      const ownerAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          signer.payer,
+          mint,
+          owner.publicKey
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        signer.payer,
+        mint,
+        ownerAta,
+        signer.payer,
+        1_000_000
+      );
+      await program.methods
+        .depositSpl(new anchor.BN(1_000_000))
+        .accounts({
+          signer: owner.publicKey,
+          mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([owner])
+        .rpc();
+      ownerVaultAta = getAssociatedTokenAddressSync(mint, ownerVault, true);
+      newVaultAta = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          signer.payer,
+          mint,
+          newVault,
+          true
+        )
+      ).address;
+    });
+
+    it("Rejects recovery approvals from non-guardians", async () => {
+      await initiate(guardianA);
+      try {
+        await approve(outsider);
+        expect.fail("Approve should fail for non-guardians");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("NotAGuardian");
+      }
+    });
+
+    it("Rejects executing a recovery below the guardian threshold", async () => {
+      try {
+        await execute();
+        expect.fail("Execute should fail below threshold");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("RecoveryNotReady");
+      }
+    });
+
+    it("Rejects changing guardians while a recovery is pending", async () => {
+      try {
+        await program.methods
+          .setGuardians([outsider.publicKey], 1, new anchor.BN(0))
+          .accounts({ signer: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("Set guardians should fail while a recovery is pending");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("RecoveryPending");
+      }
+    });
+
+    it("Lets the owner veto a pending recovery", async () => {
+      await program.methods
+        .vetoRecovery()
+        .accounts({ signer: owner.publicKey, initiator: guardianA.publicKey })
+        .signers([owner])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(recovery)).to.be.null;
+    });
+
+    it("Rejects migrating without every vault token account", async () => {
+      await initiate(guardianA);
+      await approve(guardianB);
+      try {
+        await execute();
+        expect.fail("Execute should fail while a token account is left out");
+      } catch (e) {
+        expect(e.error.errorCode.code).to.equal("MissingTokenAccounts");
+      }
+    });
+
+    it("Migrates the vault to the new owner once guardians approve", async () => {
+      let closedEvent;
+      const listener = program.addEventListener("closed", (event) => {
+        closedEvent = event;
+      });
+      await execute([
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: ownerVaultAta, isSigner: false, isWritable: true },
+        { pubkey: newVaultAta, isSigner: false, isWritable: true },
+      ]);
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      const vaultState = await program.account.vaultState.fetch(newVaultState);
+      expect(vaultState.balance.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(await provider.connection.getBalance(newVault)).to.equal(
+        LAMPORTS_PER_SOL
+      );
+      expect(await provider.connection.getAccountInfo(ownerVaultState)).to.be
+        .null;
+
+      // The old vault's tokens moved and its token account was closed
+      const newVaultBalance = await provider.connection.getTokenAccountBalance(
+        newVaultAta
+      );
+      expect(newVaultBalance.value.amount).to.equal("1000000");
+      expect(await provider.connection.getAccountInfo(ownerVaultAta)).to.be.null;
+      expect(vaultState.tokenAccounts).to.equal(1);
 
-    // Log final state
-    console.log("Vault info after close:", vaultInfoAfterClose);
+      expect(closedEvent.vaultState.toBase58()).to.equal(
+        ownerVaultState.toBase58()
+      );
+      expect(closedEvent.owner.toBase58()).to.equal(owner.publicKey.toBase58());
+    });
   });
 });
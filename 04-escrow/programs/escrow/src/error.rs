@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Deposit amount must be greater than zero.")]
+    InvalidDepositAmount,
+    #[msg("Receive amount must be greater than zero.")]
+    InvalidReceiveAmount,
+    #[msg("Mint A and mint B must be different.")]
+    SameMint,
+    #[msg("An escrow with this seed already exists.")]
+    SeedAlreadyInUse,
+    #[msg("Floor price must be greater than zero.")]
+    InvalidFloorPrice,
+    #[msg("Start price must be greater than the floor price.")]
+    InvalidAuctionPrice,
+    #[msg("Auction duration must be greater than zero.")]
+    InvalidAuctionDuration,
+    #[msg("Overflow detected.")]
+    Overflow,
+    #[msg("NFT is not part of the verified collection requested by the escrow.")]
+    InvalidCollection,
+    #[msg("Escrow requests an NFT from a collection, use take_from_collection.")]
+    CollectionOfferRequiresNft,
+    #[msg("Escrow does not request an NFT from a collection.")]
+    NotACollectionOffer,
+}
@@ -1,11 +1,16 @@
 use anchor_lang::prelude::*;
 
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{
+        create_idempotent, get_associated_token_address_with_program_id, AssociatedToken, Create,
+    },
     token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
-use crate::Escrow;
+use crate::{error::EscrowError, DutchAuction, Escrow, COLLECTION_OFFER_RECEIVE};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
@@ -17,7 +22,7 @@ pub struct Make<'info> {
     )]
     pub mint_a: InterfaceAccount<'info, Mint>,
     #[account(
-      mint::token_program = token_program
+      constraint = mint_a.key() != mint_b.key() @ EscrowError::SameMint
     )]
     pub mint_b: InterfaceAccount<'info, Mint>,
     #[account(
@@ -27,28 +32,56 @@ pub struct Make<'info> {
       associated_token::token_program = token_program
     )]
     pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    // Created in the handler: `init` would run before any constraint and hide a
+    // taken seed behind the system program's "already in use"
+    /// CHECK: must be the empty escrow PDA for this seed
     #[account(
-      init,
-      payer = maker,
-      space = 8 + Escrow::INIT_SPACE,
+      mut,
       seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
-      bump
+      bump,
+      constraint = escrow.data_is_empty() @ EscrowError::SeedAlreadyInUse
     )]
-    pub escrow: Account<'info, Escrow>,
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: the escrow's ATA for mint_a, created in the handler
     #[account(
-      init,
-      payer = maker,
-      associated_token::mint = mint_a,
-      associated_token::authority = escrow,
-      associated_token::token_program = token_program
+      mut,
+      address = get_associated_token_address_with_program_id(&escrow.key(), &mint_a.key(), &token_program.key())
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub vault: UncheckedAccount<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl Make<'_> {
+    pub fn validate(&self, deposit: u64, receive: u64) -> Result<()> {
+        self.validate_deposit(deposit)?;
+        require!(receive > 0, EscrowError::InvalidReceiveAmount);
+
+        Ok(())
+    }
+
+    pub fn validate_deposit(&self, deposit: u64) -> Result<()> {
+        require!(deposit > 0, EscrowError::InvalidDepositAmount);
+
+        Ok(())
+    }
+
+    pub fn create_vault(&self) -> Result<()> {
+        let accounts = Create {
+            payer: self.maker.to_account_info(),
+            associated_token: self.vault.to_account_info(),
+            authority: self.escrow.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.associated_token_program.to_account_info(), accounts);
+
+        create_idempotent(ctx)
+    }
+
     pub fn deposit(&mut self, deposit: u64) -> Result<()> {
         let transfer_accounts = TransferChecked {
             from: self.maker_ata_a.to_account_info(),
@@ -62,16 +95,136 @@ impl Make<'_> {
         transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
     }
 
-    pub fn save_escrow(&mut self, seed: u64, bumps: &MakeBumps, receive: u64) -> Result<()> {
-        self.escrow.set_inner(Escrow {
+    pub fn save_escrow(&self, seed: u64, bumps: &MakeBumps, receive: u64) -> Result<()> {
+        self.check_mint_b_token_program()?;
+
+        self.create_escrow(Escrow {
             seed,
             bump: bumps.escrow,
             maker: self.maker.key(),
             mint_a: self.mint_a.key(),
             mint_b: self.mint_b.key(),
             receive,
-        });
+            auction: None,
+            collection: None,
+        })
+    }
+
+    pub fn save_auction(
+        &self,
+        seed: u64,
+        bumps: &MakeBumps,
+        start_price: u64,
+        floor_price: u64,
+        duration: i64,
+    ) -> Result<()> {
+        self.check_mint_b_token_program()?;
+        require!(floor_price > 0, EscrowError::InvalidFloorPrice);
+        require!(start_price > floor_price, EscrowError::InvalidAuctionPrice);
+        require!(duration > 0, EscrowError::InvalidAuctionDuration);
+
+        let start_time = Clock::get()?.unix_timestamp;
+        let end_time = start_time
+            .checked_add(duration)
+            .ok_or(EscrowError::Overflow)?;
+
+        self.create_escrow(Escrow {
+            seed,
+            bump: bumps.escrow,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            receive: start_price,
+            auction: Some(DutchAuction {
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+            }),
+            collection: None,
+        })
+    }
+
+    // mint_b is the collection mint, any NFT verified as part of it fills the offer
+    pub fn save_collection_offer(&self, seed: u64, bumps: &MakeBumps) -> Result<()> {
+        self.create_escrow(Escrow {
+            seed,
+            bump: bumps.escrow,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            receive: COLLECTION_OFFER_RECEIVE,
+            auction: None,
+            collection: Some(self.mint_b.key()),
+        })
+    }
+
+    // Take pays mint_b through the same token_program as mint_a. Only a collection
+    // offer's mint_b, which is never transferred, may live on another program
+    fn check_mint_b_token_program(&self) -> Result<()> {
+        require_keys_eq!(
+            *self.mint_b.to_account_info().owner,
+            self.token_program.key(),
+            anchor_lang::error::ErrorCode::ConstraintMintTokenProgram
+        );
 
         Ok(())
     }
+
+    // Same steps as Anchor's `init`, so lamports sent to the PDA ahead of time don't block it
+    fn create_escrow(&self, escrow: Escrow) -> Result<()> {
+        let space = 8 + Escrow::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+        let lamports = self.escrow.lamports();
+
+        let seed = escrow.seed.to_le_bytes();
+        let maker = self.maker.key();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"escrow", maker.as_ref(), seed.as_ref(), &[escrow.bump]]];
+
+        if lamports == 0 {
+            let accounts = CreateAccount {
+                from: self.maker.to_account_info(),
+                to: self.escrow.to_account_info(),
+            };
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+            create_account(ctx, rent, space as u64, &crate::ID)?;
+        } else {
+            if lamports < rent {
+                let accounts = Transfer {
+                    from: self.maker.to_account_info(),
+                    to: self.escrow.to_account_info(),
+                };
+                let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+                transfer(ctx, rent - lamports)?;
+            }
+
+            let accounts = Allocate {
+                account_to_allocate: self.escrow.to_account_info(),
+            };
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+            allocate(ctx, space as u64)?;
+
+            let accounts = Assign {
+                account_to_assign: self.escrow.to_account_info(),
+            };
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+            assign(ctx, &crate::ID)?;
+        }
+
+        let mut data = self.escrow.try_borrow_mut_data()?;
+        escrow.try_serialize(&mut &mut data[..])
+    }
 }
@@ -5,4 +5,7 @@ pub mod take;
 pub use take::*;
 
 pub mod refund;
-pub use refund::*;
\ No newline at end of file
+pub use refund::*;
+
+pub mod take_from_collection;
+pub use take_from_collection::*;
\ No newline at end of file
@@ -2,13 +2,10 @@ use anchor_lang::prelude::*;
 
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{
-        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
-        TransferChecked,
-    },
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
-use crate::Escrow;
+use crate::{error::EscrowError, vault::withdraw_and_close_vault, Escrow};
 
 #[derive(Accounts)]
 pub struct Take<'info> {
@@ -47,6 +44,7 @@ pub struct Take<'info> {
         has_one = maker,
         has_one = mint_a,
         has_one = mint_b,
+        constraint = escrow.collection.is_none() @ EscrowError::CollectionOfferRequiresNft,
         seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
         bump = escrow.bump
     )]
@@ -75,41 +73,19 @@ impl Take<'_> {
 
         let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)
+        let receive = self.escrow.receive_at(Clock::get()?.unix_timestamp)?;
+
+        transfer_checked(cpi_ctx, receive, self.mint_b.decimals)
     }
 
     pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
-        let signer_seeds: [&[&[u8]]; 1] = [&[
-            b"escrow",
-            self.maker.to_account_info().key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
-            &[self.escrow.bump],
-        ]];
-
-        // Transfer the token a from the vault to the taker
-        let accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            mint: self.mint_a.to_account_info(),
-            to: self.taker_ata_a.to_account_info(),
-            authority: self.escrow.to_account_info(),
-        };
-
-        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts)
-            .with_signer(&signer_seeds);
-
-        
-        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
-
-        // Close the vault account and send the remaining lamports to the maker
-        let accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.maker.to_account_info(),
-            authority: self.escrow.to_account_info(),
-        };
-
-        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts)
-            .with_signer(&signer_seeds);
-
-        close_account(ctx)
+        withdraw_and_close_vault(
+            &self.escrow,
+            self.maker.to_account_info(),
+            &self.mint_a,
+            &self.vault,
+            self.taker_ata_a.to_account_info(),
+            &self.token_program,
+        )
     }
 }
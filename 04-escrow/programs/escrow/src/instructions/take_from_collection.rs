@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    error::EscrowError, vault::withdraw_and_close_vault, Escrow, COLLECTION_OFFER_RECEIVE,
+};
+
+#[derive(Accounts)]
+pub struct TakeFromCollection<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = nft_token_program)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = nft_token_program,
+    )]
+    pub taker_nft_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = nft_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = nft_token_program,
+    )]
+    pub maker_nft_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = escrow.collection.is_some() @ EscrowError::NotACollectionOffer,
+        constraint = escrow.collection == Some(collection_mint.key()) @ EscrowError::InvalidCollection,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|c| c.verified && c.key == collection_mint.key())
+            @ EscrowError::InvalidCollection,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: Account<'info, MasterEditionAccount>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // The NFT side may use a different token program than mint_a
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl TakeFromCollection<'_> {
+    // Transfer the collection NFT from the taker to the maker
+    pub fn deposit_nft(&mut self) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.taker_nft_ata.to_account_info(),
+            mint: self.nft_mint.to_account_info(),
+            to: self.maker_nft_ata.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.nft_token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, COLLECTION_OFFER_RECEIVE, self.nft_mint.decimals)
+    }
+
+    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        withdraw_and_close_vault(
+            &self.escrow,
+            self.maker.to_account_info(),
+            &self.mint_a,
+            &self.vault,
+            self.taker_ata_a.to_account_info(),
+            &self.token_program,
+        )
+    }
+}
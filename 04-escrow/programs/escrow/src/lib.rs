@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 
+pub mod error;
+
 pub mod state;
 pub use state::*;
 
+pub mod vault;
+
 pub mod instructions;
 use instructions::*;
 
@@ -13,15 +17,44 @@ pub mod escrow {
     use super::*;
 
     pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
+        ctx.accounts.validate(deposit, receive)?;
+        ctx.accounts.create_vault()?;
         ctx.accounts.deposit(deposit)?;
         ctx.accounts.save_escrow(seed, &ctx.bumps, receive)
     }
 
+    pub fn make_auction(
+        ctx: Context<Make>,
+        seed: u64,
+        deposit: u64,
+        start_price: u64,
+        floor_price: u64,
+        duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.validate_deposit(deposit)?;
+        ctx.accounts.create_vault()?;
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts
+            .save_auction(seed, &ctx.bumps, start_price, floor_price, duration)
+    }
+
+    pub fn make_collection_offer(ctx: Context<Make>, seed: u64, deposit: u64) -> Result<()> {
+        ctx.accounts.validate(deposit, COLLECTION_OFFER_RECEIVE)?;
+        ctx.accounts.create_vault()?;
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts.save_collection_offer(seed, &ctx.bumps)
+    }
+
     pub fn take(ctx: Context<Take>) -> Result<()> {
         ctx.accounts.deposit()?;
         ctx.accounts.withdraw_and_close_vault()
     }
 
+    pub fn take_from_collection(ctx: Context<TakeFromCollection>) -> Result<()> {
+        ctx.accounts.deposit_nft()?;
+        ctx.accounts.withdraw_and_close_vault()
+    }
+
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
         ctx.accounts.refund_and_close_vault()
     }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::error::EscrowError;
+
+// A collection offer is filled by exactly one NFT from the collection
+pub const COLLECTION_OFFER_RECEIVE: u64 = 1;
+
 #[account]
 #[derive(InitSpace)]
 pub struct Escrow {
@@ -9,4 +14,47 @@ pub struct Escrow {
   pub mint_a: Pubkey,
   pub mint_b: Pubkey,
   pub receive: u64,
-}
\ No newline at end of file
+  pub auction: Option<DutchAuction>,
+  pub collection: Option<Pubkey>,
+}
+
+impl Escrow {
+  // Amount of token b the taker has to pay at the given time
+  pub fn receive_at(&self, now: i64) -> Result<u64> {
+    match &self.auction {
+      Some(auction) => auction.price_at(now),
+      None => Ok(self.receive),
+    }
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DutchAuction {
+  pub start_price: u64,
+  pub floor_price: u64,
+  pub start_time: i64,
+  pub end_time: i64,
+}
+
+impl DutchAuction {
+  // Price decays linearly from start_price to floor_price between start_time and end_time
+  pub fn price_at(&self, now: i64) -> Result<u64> {
+    if now <= self.start_time {
+      return Ok(self.start_price);
+    }
+    if now >= self.end_time {
+      return Ok(self.floor_price);
+    }
+
+    let elapsed = (now - self.start_time) as u128;
+    let duration = (self.end_time - self.start_time) as u128;
+    let spread = (self.start_price - self.floor_price) as u128;
+
+    let decay = spread
+      .checked_mul(elapsed)
+      .ok_or(EscrowError::Overflow)?
+      / duration;
+
+    Ok(self.start_price - decay as u64)
+  }
+}
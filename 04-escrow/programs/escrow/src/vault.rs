@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::Escrow;
+
+// Sends the whole vault to the taker and closes it to the maker, shared by take and take_from_collection
+pub fn withdraw_and_close_vault<'info>(
+    escrow: &Account<'info, Escrow>,
+    maker: AccountInfo<'info>,
+    mint_a: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    taker_ata_a: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let signer_seeds: [&[&[u8]]; 1] = [&[
+        b"escrow",
+        maker.key.as_ref(),
+        &escrow.seed.to_le_bytes()[..],
+        &[escrow.bump],
+    ]];
+
+    // Transfer the token a from the vault to the taker
+    let accounts = TransferChecked {
+        from: vault.to_account_info(),
+        mint: mint_a.to_account_info(),
+        to: taker_ata_a,
+        authority: escrow.to_account_info(),
+    };
+
+    let ctx = CpiContext::new(token_program.to_account_info(), accounts).with_signer(&signer_seeds);
+
+    transfer_checked(ctx, vault.amount, mint_a.decimals)?;
+
+    // Close the vault account and send the remaining lamports to the maker
+    let accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: maker,
+        authority: escrow.to_account_info(),
+    };
+
+    let ctx = CpiContext::new(token_program.to_account_info(), accounts).with_signer(&signer_seeds);
+
+    close_account(ctx)
+}
@@ -0,0 +1,35 @@
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+
+use crate::{Escrow, ESCROW_PROGRAM_ID};
+
+/// Offset of `Escrow.maker`: discriminator, `seed: u64` and `bump: u8` come first.
+const MAKER_OFFSET: usize = 8 + 8 + 1;
+
+/// Fetch and decode every open escrow created by `maker`.
+pub fn fetch_open_escrows(
+    client: &RpcClient,
+    maker: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, Escrow)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, Escrow::DISCRIMINATOR)),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(MAKER_OFFSET, maker.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig::default(),
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    client
+        .get_program_accounts_with_config(&ESCROW_PROGRAM_ID, config)?
+        .into_iter()
+        .map(|(address, account)| {
+            let escrow = Escrow::try_deserialize(&mut account.data.as_slice())?;
+            Ok((address, escrow))
+        })
+        .collect()
+}
@@ -0,0 +1,250 @@
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, InstructionData,
+    ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id, ID as ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    metadata::mpl_token_metadata::ID as METADATA_PROGRAM_ID,
+};
+
+use crate::{escrow_pda, master_edition_pda, metadata_pda, vault_address, ESCROW_PROGRAM_ID};
+
+fn make_accounts(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> escrow::accounts::Make {
+    let escrow = escrow_pda(maker, seed).0;
+
+    escrow::accounts::Make {
+        maker: *maker,
+        mint_a: *mint_a,
+        mint_b: *mint_b,
+        maker_ata_a: get_associated_token_address_with_program_id(maker, mint_a, token_program),
+        escrow,
+        vault: vault_address(&escrow, mint_a, token_program),
+        associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    }
+}
+
+/// Build a `make` instruction locking `deposit` of mint A in exchange for `receive` of mint B.
+pub fn make_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    deposit: u64,
+    receive: u64,
+) -> Instruction {
+    let accounts = make_accounts(maker, mint_a, mint_b, token_program, seed);
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::Make {
+            seed,
+            deposit,
+            receive,
+        }
+        .data(),
+    }
+}
+
+/// Build a `make_auction` instruction whose price decays from `start_price` to `floor_price`
+/// over `duration` seconds.
+#[allow(clippy::too_many_arguments)]
+pub fn make_auction_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    deposit: u64,
+    start_price: u64,
+    floor_price: u64,
+    duration: i64,
+) -> Instruction {
+    let accounts = make_accounts(maker, mint_a, mint_b, token_program, seed);
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::MakeAuction {
+            seed,
+            deposit,
+            start_price,
+            floor_price,
+            duration,
+        }
+        .data(),
+    }
+}
+
+/// Build a `make_collection_offer` instruction accepting any NFT from the verified
+/// `collection_mint` in exchange for `deposit` of mint A.
+pub fn make_collection_offer_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    collection_mint: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    deposit: u64,
+) -> Instruction {
+    let accounts = make_accounts(maker, mint_a, collection_mint, token_program, seed);
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::MakeCollectionOffer { seed, deposit }.data(),
+    }
+}
+
+/// Build a `take` instruction filling the maker's escrow with the given seed.
+pub fn take_ix(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = escrow_pda(maker, seed).0;
+
+    let accounts = escrow::accounts::Take {
+        taker: *taker,
+        maker: *maker,
+        mint_a: *mint_a,
+        mint_b: *mint_b,
+        taker_ata_a: get_associated_token_address_with_program_id(taker, mint_a, token_program),
+        taker_ata_b: get_associated_token_address_with_program_id(taker, mint_b, token_program),
+        maker_ata_b: get_associated_token_address_with_program_id(maker, mint_b, token_program),
+        escrow,
+        vault: vault_address(&escrow, mint_a, token_program),
+        associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::Take {}.data(),
+    }
+}
+
+/// Build a `take_from_collection` instruction filling a collection offer with `nft_mint`.
+#[allow(clippy::too_many_arguments)]
+pub fn take_from_collection_ix(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    collection_mint: &Pubkey,
+    nft_mint: &Pubkey,
+    token_program: &Pubkey,
+    nft_token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = escrow_pda(maker, seed).0;
+
+    let accounts = escrow::accounts::TakeFromCollection {
+        taker: *taker,
+        maker: *maker,
+        mint_a: *mint_a,
+        collection_mint: *collection_mint,
+        nft_mint: *nft_mint,
+        taker_ata_a: get_associated_token_address_with_program_id(taker, mint_a, token_program),
+        taker_nft_ata: get_associated_token_address_with_program_id(
+            taker,
+            nft_mint,
+            nft_token_program,
+        ),
+        maker_nft_ata: get_associated_token_address_with_program_id(
+            maker,
+            nft_mint,
+            nft_token_program,
+        ),
+        escrow,
+        vault: vault_address(&escrow, mint_a, token_program),
+        metadata: metadata_pda(nft_mint),
+        master_edition: master_edition_pda(nft_mint),
+        metadata_program: METADATA_PROGRAM_ID,
+        associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+        token_program: *token_program,
+        nft_token_program: *nft_token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::TakeFromCollection {}.data(),
+    }
+}
+
+/// Build a `refund` instruction returning the vault balance to the maker.
+pub fn refund_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = escrow_pda(maker, seed).0;
+
+    let accounts = escrow::accounts::Refund {
+        maker: *maker,
+        mint_a: *mint_a,
+        maker_ata_a: get_associated_token_address_with_program_id(maker, mint_a, token_program),
+        escrow,
+        vault: vault_address(&escrow, mint_a, token_program),
+        associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+        token_program: *token_program,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: ESCROW_PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: escrow::instruction::Refund {}.data(),
+    }
+}
+
+#[cfg(test)]
+mod instructions_tests {
+    use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+
+    use super::*;
+
+    #[test]
+    fn make_ix_signs_with_maker_and_targets_escrow_pda() {
+        let (maker, mint_a, mint_b) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = make_ix(&maker, &mint_a, &mint_b, &TOKEN_PROGRAM_ID, 7, 100, 200);
+
+        assert_eq!(ix.program_id, ESCROW_PROGRAM_ID);
+        assert_eq!(ix.accounts[0].pubkey, maker);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[4].pubkey, escrow_pda(&maker, 7).0);
+    }
+
+    #[test]
+    fn take_and_refund_share_the_vault() {
+        let (maker, taker) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let take = take_ix(&taker, &maker, &mint_a, &mint_b, &TOKEN_PROGRAM_ID, 7);
+        let refund = refund_ix(&maker, &mint_a, &TOKEN_PROGRAM_ID, 7);
+
+        let vault = vault_address(&escrow_pda(&maker, 7).0, &mint_a, &TOKEN_PROGRAM_ID);
+        assert_eq!(take.accounts[8].pubkey, vault);
+        assert_eq!(refund.accounts[4].pubkey, vault);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod fetch;
+pub use fetch::*;
+
+pub mod instructions;
+pub use instructions::*;
+
+pub mod pda;
+pub use pda::*;
+
+pub use escrow::{state::Escrow, ID as ESCROW_PROGRAM_ID};
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    metadata::mpl_token_metadata::accounts::{MasterEdition, Metadata},
+};
+
+use crate::ESCROW_PROGRAM_ID;
+
+/// Derive the `escrow` PDA for a maker and seed.
+pub fn escrow_pda(maker: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"escrow", maker.as_ref(), seed.to_le_bytes().as_ref()],
+        &ESCROW_PROGRAM_ID,
+    )
+}
+
+/// Derive the vault ATA holding mint A on behalf of an escrow.
+pub fn vault_address(escrow: &Pubkey, mint_a: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(escrow, mint_a, token_program)
+}
+
+/// Derive the Metaplex metadata account of a mint.
+pub fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    Metadata::find_pda(mint).0
+}
+
+/// Derive the Metaplex master edition account of a mint.
+pub fn master_edition_pda(mint: &Pubkey) -> Pubkey {
+    MasterEdition::find_pda(mint).0
+}
+
+#[cfg(test)]
+mod pda_tests {
+    use super::*;
+
+    #[test]
+    fn escrow_pda_depends_on_maker_and_seed() {
+        let maker = Pubkey::new_unique();
+        let (escrow, _) = escrow_pda(&maker, 1);
+
+        assert_eq!(escrow, escrow_pda(&maker, 1).0);
+        assert_ne!(escrow, escrow_pda(&maker, 2).0);
+        assert_ne!(escrow, escrow_pda(&Pubkey::new_unique(), 1).0);
+    }
+}
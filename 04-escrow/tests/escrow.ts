@@ -11,14 +11,22 @@ import {
 import {
   MINT_SIZE,
   TOKEN_2022_PROGRAM_ID,
-  // TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountIdempotentInstruction,
   createInitializeMint2Instruction,
   createMintToInstruction,
   getAssociatedTokenAddressSync,
   getMinimumBalanceForRentExemptMint,
 } from "@solana/spl-token";
+import {
+  PROGRAM_ID as METADATA_PROGRAM_ID,
+  createCreateMetadataAccountV3Instruction,
+  createCreateMasterEditionV3Instruction,
+  createVerifyCollectionInstruction,
+  DataV2,
+} from "@metaplex-foundation/mpl-token-metadata";
 import { randomBytes } from "crypto";
+import { expect } from "chai";
 
 describe("escrow", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -83,6 +91,16 @@ describe("escrow", () => {
     tokenProgram,
   );
 
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal(code);
+      return;
+    }
+    expect.fail(`Expected ${code} error`);
+  };
+
   // Accounts
   const accounts = {
     maker: maker.publicKey,
@@ -150,6 +168,39 @@ describe("escrow", () => {
     await provider.sendAndConfirm(tx, [mintA, mintB, maker, taker]).then(log);
   });
 
+  it("Fails to create escrow with zero deposit", async () => {
+    await expectError(
+      program.methods
+        .make(seed, new BN(0), new BN(1e6))
+        .accounts({ ...accounts })
+        .signers([maker])
+        .rpc(),
+      "InvalidDepositAmount",
+    );
+  });
+
+  it("Fails to create escrow with zero receive", async () => {
+    await expectError(
+      program.methods
+        .make(seed, new BN(1e6), new BN(0))
+        .accounts({ ...accounts })
+        .signers([maker])
+        .rpc(),
+      "InvalidReceiveAmount",
+    );
+  });
+
+  it("Fails to create escrow with the same mint on both sides", async () => {
+    await expectError(
+      program.methods
+        .make(seed, new BN(1e6), new BN(1e6))
+        .accounts({ ...accounts, mintB: mintA.publicKey })
+        .signers([maker])
+        .rpc(),
+      "SameMint",
+    );
+  });
+
   it("Create escrow", async () => {
     await program.methods
       .make(seed, new BN(1e6), new BN(1e6))
@@ -160,6 +211,17 @@ describe("escrow", () => {
       .then(log);
   });
 
+  it("Fails to create escrow with a seed already in use", async () => {
+    await expectError(
+      program.methods
+        .make(seed, new BN(1e6), new BN(1e6))
+        .accounts({ ...accounts })
+        .signers([maker])
+        .rpc(),
+      "SeedAlreadyInUse",
+    );
+  });
+
   it("Refund", async () => {
     await program.methods
       .refund()
@@ -194,4 +256,394 @@ describe("escrow", () => {
       throw e;
     }
   });
+
+  describe("dutch auction", () => {
+    const auctionSeed = new BN(randomBytes(8));
+
+    const auctionEscrow = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("escrow"),
+        maker.publicKey.toBuffer(),
+        auctionSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId,
+    )[0];
+
+    const auctionAccounts = {
+      ...accounts,
+      escrow: auctionEscrow,
+      vault: getAssociatedTokenAddressSync(
+        mintA.publicKey,
+        auctionEscrow,
+        true,
+        tokenProgram,
+      ),
+    };
+
+    it("Fails to create an auction below the floor price", async () => {
+      await expectError(
+        program.methods
+          .makeAuction(
+            auctionSeed,
+            new BN(1e6),
+            new BN(1e5),
+            new BN(1e6),
+            new BN(60),
+          )
+          .accounts({ ...auctionAccounts })
+          .signers([maker])
+          .rpc(),
+        "InvalidAuctionPrice",
+      );
+    });
+
+    it("Fails to create an auction with a zero floor price", async () => {
+      await expectError(
+        program.methods
+          .makeAuction(
+            auctionSeed,
+            new BN(1e6),
+            new BN(1e6),
+            new BN(0),
+            new BN(60),
+          )
+          .accounts({ ...auctionAccounts })
+          .signers([maker])
+          .rpc(),
+        "InvalidFloorPrice",
+      );
+    });
+
+    it("Fails to create an auction without a duration", async () => {
+      await expectError(
+        program.methods
+          .makeAuction(
+            auctionSeed,
+            new BN(1e6),
+            new BN(1e6),
+            new BN(1e5),
+            new BN(0),
+          )
+          .accounts({ ...auctionAccounts })
+          .signers([maker])
+          .rpc(),
+        "InvalidAuctionDuration",
+      );
+    });
+
+    it("Create an auction", async () => {
+      await program.methods
+        .makeAuction(
+          auctionSeed,
+          new BN(1e6),
+          new BN(2e6),
+          new BN(1e6),
+          new BN(60),
+        )
+        .accounts({ ...auctionAccounts })
+        .signers([maker])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const escrowAccount = await program.account.escrow.fetch(auctionEscrow);
+      expect(escrowAccount.auction.startPrice.toNumber()).to.equal(2e6);
+      expect(escrowAccount.auction.floorPrice.toNumber()).to.equal(1e6);
+    });
+
+    it("Take the auction at a price between start and floor", async () => {
+      const before = await connection.getTokenAccountBalance(takerAtaB);
+
+      await program.methods
+        .take()
+        .accounts({ ...auctionAccounts })
+        .signers([taker])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const after = await connection.getTokenAccountBalance(takerAtaB);
+      const paid = Number(before.value.amount) - Number(after.value.amount);
+      expect(paid).to.be.at.most(2e6);
+      expect(paid).to.be.at.least(1e6);
+    });
+  });
+
+  describe("collection offer", () => {
+    const offerSeed = new BN(randomBytes(8));
+
+    const offerEscrow = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("escrow"),
+        maker.publicKey.toBuffer(),
+        offerSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId,
+    )[0];
+
+    const offerAccounts = {
+      ...accounts,
+      escrow: offerEscrow,
+      vault: getAssociatedTokenAddressSync(
+        mintA.publicKey,
+        offerEscrow,
+        true,
+        tokenProgram,
+      ),
+    };
+
+    it("Create a collection offer", async () => {
+      await program.methods
+        .makeCollectionOffer(offerSeed, new BN(1e6))
+        .accounts({ ...offerAccounts })
+        .signers([maker])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const escrowAccount = await program.account.escrow.fetch(offerEscrow);
+      expect(escrowAccount.collection.toBase58()).to.equal(
+        mintB.publicKey.toBase58(),
+      );
+    });
+
+    it("Fails to take a collection offer with the collection mint", async () => {
+      await expectError(
+        program.methods
+          .take()
+          .accounts({ ...offerAccounts })
+          .signers([taker])
+          .rpc(),
+        "CollectionOfferRequiresNft",
+      );
+    });
+
+    it("Refund the collection offer", async () => {
+      await program.methods
+        .refund()
+        .accounts({ ...offerAccounts })
+        .signers([maker])
+        .rpc()
+        .then(confirm)
+        .then(log);
+    });
+  });
+
+  describe("verified collection NFT", () => {
+    // The NFT side uses the legacy token program while mint A is on Token-2022
+    const nftTokenProgram = TOKEN_PROGRAM_ID;
+    const offerSeed = new BN(randomBytes(8));
+    const deposit = 1e6;
+    const [collectionMint, nftMint] = [Keypair.generate(), Keypair.generate()];
+
+    const metadataPda = (mint: PublicKey, ...extra: Buffer[]) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          METADATA_PROGRAM_ID.toBuffer(),
+          mint.toBuffer(),
+          ...extra,
+        ],
+        METADATA_PROGRAM_ID,
+      )[0];
+
+    const offerEscrow = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("escrow"),
+        maker.publicKey.toBuffer(),
+        offerSeed.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId,
+    )[0];
+
+    const [takerNftAta, makerNftAta] = [taker, maker].map((a) =>
+      getAssociatedTokenAddressSync(
+        nftMint.publicKey,
+        a.publicKey,
+        false,
+        nftTokenProgram,
+      ),
+    );
+
+    const offerAccounts = {
+      ...accounts,
+      mintB: collectionMint.publicKey,
+      escrow: offerEscrow,
+      vault: getAssociatedTokenAddressSync(
+        mintA.publicKey,
+        offerEscrow,
+        true,
+        tokenProgram,
+      ),
+    };
+
+    // Mint a master edition with the given metadata to the taker
+    const createNftInstructions = async (
+      mint: Keypair,
+      data: DataV2,
+      collectionDetails: { __kind: "V1"; size: number } | null,
+    ) => {
+      const ata = getAssociatedTokenAddressSync(
+        mint.publicKey,
+        taker.publicKey,
+        false,
+        nftTokenProgram,
+      );
+
+      return [
+        SystemProgram.createAccount({
+          fromPubkey: taker.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space: MINT_SIZE,
+          lamports: await getMinimumBalanceForRentExemptMint(connection),
+          programId: nftTokenProgram,
+        }),
+        createInitializeMint2Instruction(
+          mint.publicKey,
+          0,
+          taker.publicKey,
+          taker.publicKey,
+          nftTokenProgram,
+        ),
+        createAssociatedTokenAccountIdempotentInstruction(
+          taker.publicKey,
+          ata,
+          taker.publicKey,
+          mint.publicKey,
+          nftTokenProgram,
+        ),
+        createMintToInstruction(
+          mint.publicKey,
+          ata,
+          taker.publicKey,
+          1,
+          [],
+          nftTokenProgram,
+        ),
+        createCreateMetadataAccountV3Instruction(
+          {
+            metadata: metadataPda(mint.publicKey),
+            mint: mint.publicKey,
+            mintAuthority: taker.publicKey,
+            payer: taker.publicKey,
+            updateAuthority: taker.publicKey,
+          },
+          {
+            createMetadataAccountArgsV3: {
+              data,
+              isMutable: true,
+              collectionDetails,
+            },
+          },
+        ),
+        createCreateMasterEditionV3Instruction(
+          {
+            edition: metadataPda(mint.publicKey, Buffer.from("edition")),
+            mint: mint.publicKey,
+            updateAuthority: taker.publicKey,
+            mintAuthority: taker.publicKey,
+            payer: taker.publicKey,
+            metadata: metadataPda(mint.publicKey),
+          },
+          { createMasterEditionArgs: { maxSupply: 0 } },
+        ),
+      ];
+    };
+
+    it("Create a verified collection NFT", async () => {
+      const tx = new Transaction().add(
+        ...(await createNftInstructions(
+          collectionMint,
+          {
+            name: "Escrow Collection",
+            symbol: "ESC",
+            uri: "https://test.uri/collection.json",
+            sellerFeeBasisPoints: 0,
+            creators: null,
+            collection: null,
+            uses: null,
+          },
+          { __kind: "V1", size: 0 },
+        )),
+        ...(await createNftInstructions(
+          nftMint,
+          {
+            name: "Escrow NFT",
+            symbol: "ESC",
+            uri: "https://test.uri/nft.json",
+            sellerFeeBasisPoints: 0,
+            creators: null,
+            collection: { key: collectionMint.publicKey, verified: false },
+            uses: null,
+          },
+          null,
+        )),
+        createVerifyCollectionInstruction({
+          metadata: metadataPda(nftMint.publicKey),
+          collectionAuthority: taker.publicKey,
+          payer: taker.publicKey,
+          collectionMint: collectionMint.publicKey,
+          collection: metadataPda(collectionMint.publicKey),
+          collectionMasterEditionAccount: metadataPda(
+            collectionMint.publicKey,
+            Buffer.from("edition"),
+          ),
+        }),
+      );
+
+      await provider
+        .sendAndConfirm(tx, [taker, collectionMint, nftMint])
+        .then(log);
+    });
+
+    it("Take a collection offer with a verified NFT", async () => {
+      await program.methods
+        .makeCollectionOffer(offerSeed, new BN(deposit))
+        .accounts({ ...offerAccounts })
+        .signers([maker])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const before = await connection.getTokenAccountBalance(takerAtaA);
+
+      await program.methods
+        .takeFromCollection()
+        .accountsPartial({
+          taker: taker.publicKey,
+          maker: maker.publicKey,
+          mintA: mintA.publicKey,
+          collectionMint: collectionMint.publicKey,
+          nftMint: nftMint.publicKey,
+          takerAtaA,
+          takerNftAta,
+          makerNftAta,
+          escrow: offerEscrow,
+          vault: offerAccounts.vault,
+          metadata: metadataPda(nftMint.publicKey),
+          masterEdition: metadataPda(
+            nftMint.publicKey,
+            Buffer.from("edition"),
+          ),
+          tokenProgram,
+          nftTokenProgram,
+        })
+        .signers([taker])
+        .rpc()
+        .then(confirm)
+        .then(log);
+
+      const after = await connection.getTokenAccountBalance(takerAtaA);
+      expect(Number(after.value.amount) - Number(before.value.amount)).to.equal(
+        deposit,
+      );
+      expect(
+        (await connection.getTokenAccountBalance(makerNftAta)).value.amount,
+      ).to.equal("1");
+      expect(
+        (await connection.getTokenAccountBalance(takerNftAta)).value.amount,
+      ).to.equal("0");
+      expect(await connection.getAccountInfo(offerEscrow)).to.be.null;
+    });
+  });
 });
@@ -0,0 +1,179 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+use solana_keccak_hasher::hashv;
+
+// Metaplex Bubblegum, owner of compressed NFT trees
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+// SPL Account Compression, which stores the merkle trees
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+// SPL Noop, Bubblegum logs leaf changes through it
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// Anchor sighash of Bubblegum's `transfer` instruction
+const TRANSFER_DISCRIMINATOR: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+
+// Leaf as Bubblegum's transfer expects it, proof nodes come in as remaining accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeafArgs {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+// Bubblegum's MetadataArgs, field order must match so the borsh bytes hash the same
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+// Compressed NFT creator, committed to by the leaf's creator_hash
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+// data_hash Bubblegum computes for the metadata
+pub fn hash_metadata(metadata: &MetadataArgs) -> Result<[u8; 32]> {
+    let metadata_hash = hashv(&[&metadata.try_to_vec()?]);
+    Ok(hashv(&[
+        metadata_hash.as_ref(),
+        &metadata.seller_fee_basis_points.to_le_bytes(),
+    ])
+    .to_bytes())
+}
+
+// creator_hash Bubblegum computes for the creators
+pub fn hash_creators(creators: &[Creator]) -> [u8; 32] {
+    let creator_data: Vec<Vec<u8>> = creators
+        .iter()
+        .map(|creator| {
+            [
+                creator.address.as_ref(),
+                &[creator.verified as u8],
+                &[creator.share],
+            ]
+            .concat()
+        })
+        .collect();
+    let slices: Vec<&[u8]> = creator_data.iter().map(Vec::as_slice).collect();
+    hashv(&slices).to_bytes()
+}
+
+// Asset id Bubblegum assigns to the leaf minted at nonce in merkle_tree
+pub fn asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+pub struct CnftTransfer<'a, 'info> {
+    pub tree_config: &'a AccountInfo<'info>,
+    pub leaf_owner: &'a AccountInfo<'info>,
+    pub new_leaf_owner: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub bubblegum_program: &'a AccountInfo<'info>,
+}
+
+// The owner doubles as the leaf delegate, so leaves with a separate delegate fail the proof
+pub fn transfer<'info>(
+    accounts: CnftTransfer<'_, 'info>,
+    leaf: &LeafArgs,
+    proof: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut metas = vec![
+        AccountMeta::new_readonly(accounts.tree_config.key(), false),
+        AccountMeta::new_readonly(accounts.leaf_owner.key(), true),
+        AccountMeta::new_readonly(accounts.leaf_owner.key(), false),
+        AccountMeta::new_readonly(accounts.new_leaf_owner.key(), false),
+        AccountMeta::new(accounts.merkle_tree.key(), false),
+        AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+        AccountMeta::new_readonly(accounts.compression_program.key(), false),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    metas.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+
+    let mut data = TRANSFER_DISCRIMINATOR.to_vec();
+    leaf.serialize(&mut data)?;
+
+    let mut infos = vec![
+        accounts.tree_config.clone(),
+        accounts.leaf_owner.clone(),
+        accounts.leaf_owner.clone(),
+        accounts.new_leaf_owner.clone(),
+        accounts.merkle_tree.clone(),
+        accounts.log_wrapper.clone(),
+        accounts.compression_program.clone(),
+        accounts.system_program.clone(),
+        accounts.bubblegum_program.clone(),
+    ];
+    infos.extend_from_slice(proof);
+
+    let instruction = Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: metas,
+        data,
+    };
+    invoke_signed(&instruction, &infos, signer_seeds).map_err(Into::into)
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::bubblegum::MetadataArgs;
+
+// NFT is a verified member of the collection
+pub fn is_verified_member(metadata: &MetadataAccount, collection_mint: &Pubkey) -> bool {
+    metadata
+        .collection
+        .as_ref()
+        .is_some_and(|collection| collection.verified && collection.key == *collection_mint)
+}
+
+// Compressed NFT is a verified member of the collection
+pub fn is_verified_cnft_member(metadata: &MetadataArgs, collection_mint: &Pubkey) -> bool {
+    metadata
+        .collection
+        .as_ref()
+        .is_some_and(|collection| collection.verified && collection.key == *collection_mint)
+}
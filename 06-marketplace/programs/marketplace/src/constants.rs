@@ -2,3 +2,7 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Basis points denominator used for marketplace fees.
+#[constant]
+pub const MAX_FEE_BPS: u16 = 10_000;
@@ -1,7 +1,61 @@
 use anchor_lang::prelude::*;
 
 #[error_code]
-pub enum ErrorCode {
-    #[msg("Custom error message")]
-    CustomError,
+pub enum MarketplaceError {
+    #[msg("Fee must be between 0 and 10000 basis points")]
+    InvalidFee,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("A verified creator account is missing from the remaining accounts")]
+    MissingCreatorAccount,
+    #[msg("Creator account does not match the NFT metadata")]
+    InvalidCreatorAccount,
+    #[msg("Only the marketplace admin can do this")]
+    Unauthorized,
+    #[msg("Treasury does not hold enough lamports")]
+    InsufficientTreasuryFunds,
+    #[msg("Withdrawal amount must be greater than zero")]
+    InvalidWithdrawAmount,
+    #[msg("Withdrawal must empty the treasury or leave it rent exempt")]
+    TreasuryBelowRent,
+    #[msg("Price must be greater than zero")]
+    InvalidPrice,
+    #[msg("Payment does not match the listing's payment mint")]
+    PaymentMintMismatch,
+    #[msg("Offer amount must be greater than zero")]
+    InvalidOfferAmount,
+    #[msg("Either the listing and its vault or the maker's token account is required")]
+    MissingNftAccount,
+    #[msg("NFT is not a verified member of the collection")]
+    InvalidCollection,
+    #[msg("Bid price and quantity must be greater than zero")]
+    InvalidBid,
+    #[msg("Not available on auction listings")]
+    AuctionListing,
+    #[msg("Listing is not an auction")]
+    NotAnAuction,
+    #[msg("Auction duration must be positive and the extension non-negative")]
+    InvalidAuctionDuration,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Bid must meet the reserve and beat the highest bid")]
+    BidTooLow,
+    #[msg("Previous bidder account does not match the highest bidder")]
+    InvalidPreviousBidder,
+    #[msg("Recipient must be the winning bidder, or the maker if there were no bids")]
+    InvalidAuctionRecipient,
+    #[msg("Auction with bids can only be settled")]
+    AuctionHasBids,
+    #[msg("Asset id does not match the merkle tree and leaf nonce")]
+    InvalidAssetId,
+    #[msg("Metadata does not hash to the compressed NFT's leaf")]
+    InvalidCnftMetadata,
+    #[msg("New admin must be set and differ from the current admin")]
+    InvalidNewAdmin,
+    #[msg("Only the pending admin can accept the admin role")]
+    NotPendingAdmin,
+    #[msg("Listing price does not match the price the buyer expected")]
+    PriceMismatch,
 }
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PriceUpdated {
+    pub listing: Pubkey,
+    pub maker_mint: Pubkey,
+    pub old_price: u64,
+    pub new_price: u64,
+}
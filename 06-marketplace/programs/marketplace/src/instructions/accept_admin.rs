@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, state::Marketplace};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    #[account(
+      mut,
+      constraint = marketplace.pending_admin == Some(new_admin.key()) @ MarketplaceError::NotPendingAdmin,
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+impl AcceptAdmin<'_> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        self.marketplace.admin = self.new_admin.key();
+        self.marketplace.pending_admin = None;
+
+        Ok(())
+    }
+}
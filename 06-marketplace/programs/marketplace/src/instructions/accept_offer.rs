@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    collection::is_verified_member,
+    error::MarketplaceError,
+    listing_vault::{close_listing_vault, send_listed_nft},
+    royalties::pay_royalties_from_escrow,
+    state::{ApprovedCollection, Listing, Marketplace, Offer},
+};
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      mut,
+      close = bidder,
+      has_one = bidder,
+      has_one = maker_mint,
+      seeds = [
+        b"offer",
+        marketplace.key().as_ref(),
+        maker_mint.key().as_ref(),
+        bidder.key().as_ref(),
+      ],
+      bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+    #[account(
+      init_if_needed,
+      payer = maker,
+      associated_token::mint = maker_mint,
+      associated_token::authority = bidder,
+    )]
+    pub bidder_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Unlisted NFTs are sent straight from the maker's wallet
+    #[account(
+      mut,
+      associated_token::mint = maker_mint,
+      associated_token::authority = maker,
+    )]
+    pub maker_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Listed NFTs are pulled from the listing vault, and the listing is closed
+    #[account(
+      mut,
+      close = maker,
+      has_one = maker,
+      has_one = maker_mint,
+      constraint = !listing.is_auction() @ MarketplaceError::AuctionListing,
+      seeds = [b"listing", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Option<Account<'info, Listing>>,
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Unlisted NFTs from an approved collection pay its fee override
+    #[account(
+      seeds = [b"collection", marketplace.key().as_ref(), approved_collection.collection_mint.as_ref()],
+      bump = approved_collection.bump,
+      constraint = is_verified_member(&metadata, &approved_collection.collection_mint) @ MarketplaceError::InvalidCollection,
+    )]
+    pub approved_collection: Option<Box<Account<'info, ApprovedCollection>>>,
+
+    #[account(
+      mut,
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+      seeds = [
+        b"metadata",
+        metadata_program.key().as_ref(),
+        maker_mint.key().as_ref(),
+      ],
+      seeds::program = metadata_program.key(),
+      bump,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>, // Metaplex program
+    pub associated_token_program: Program<'info, AssociatedToken>, // for creating associated token accounts
+    pub system_program: Program<'info, System>,                    // for creating accounts
+    pub token_program: Interface<'info, TokenInterface>,           // for token operations
+}
+
+impl<'info> AcceptOffer<'info> {
+    // Split the escrowed offer between creators, the treasury and the maker. The
+    // offer account keeps its rent, which goes back to the bidder on close.
+    pub fn send_sol(&mut self, creators: &[AccountInfo<'info>]) -> Result<()> {
+        let amount = self.offer.amount;
+        // Listings carry the override they were listed with, unlisted NFTs take it
+        // from the approved collection when one is passed
+        let fee_override = match (&self.listing, &self.approved_collection) {
+            (Some(listing), _) => listing.fee_override,
+            (None, Some(approved_collection)) => approved_collection.fee_override,
+            (None, None) => None,
+        };
+        let marketplace_fee = self.marketplace.fee_for(amount, fee_override)?;
+
+        let royalties = pay_royalties_from_escrow(
+            &self.offer.to_account_info(),
+            &self.metadata,
+            amount,
+            creators,
+        )?;
+
+        let proceeds = amount
+            .checked_sub(marketplace_fee)
+            .and_then(|proceeds| proceeds.checked_sub(royalties))
+            .ok_or(MarketplaceError::Overflow)?;
+
+        self.offer.sub_lamports(marketplace_fee)?;
+        self.treasury.add_lamports(marketplace_fee)?;
+
+        self.offer.sub_lamports(proceeds)?;
+        self.maker.add_lamports(proceeds)?;
+
+        Ok(())
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {
+        match (&self.listing, &self.vault, &self.maker_ata) {
+            (Some(listing), Some(vault), _) => {
+                require_keys_eq!(
+                    vault.owner,
+                    listing.key(),
+                    MarketplaceError::MissingNftAccount
+                );
+
+                // Transfer the NFT out of the listing vault
+                send_listed_nft(
+                    &self.marketplace.key(),
+                    listing,
+                    &self.maker_mint,
+                    vault,
+                    self.bidder_ata.to_account_info(),
+                    &self.token_program,
+                )?;
+
+                // Close the vault, returning its rent to the maker
+                close_listing_vault(
+                    &self.marketplace.key(),
+                    listing,
+                    vault,
+                    self.maker.to_account_info(),
+                    &self.token_program,
+                )
+            }
+            (None, _, Some(maker_ata)) => {
+                let cpi_accounts = TransferChecked {
+                    from: maker_ata.to_account_info(),
+                    mint: self.maker_mint.to_account_info(),
+                    to: self.bidder_ata.to_account_info(),
+                    authority: self.maker.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+                transfer_checked(cpi_ctx, 1, self.maker_mint.decimals)
+            }
+            _ => err!(MarketplaceError::MissingNftAccount),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::MarketplaceError,
+    state::{ApprovedCollection, Marketplace},
+    MAX_FEE_BPS,
+};
+
+#[derive(Accounts)]
+pub struct ApproveCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+      has_one = admin @ MarketplaceError::Unauthorized,
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    // Approving an already approved collection updates its fee override
+    #[account(
+      init_if_needed,
+      payer = admin,
+      seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+      bump,
+      space = ApprovedCollection::INIT_SPACE
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ApproveCollection<'_> {
+    pub fn approve(
+        &mut self,
+        fee_override: Option<u16>,
+        bumps: &ApproveCollectionBumps,
+    ) -> Result<()> {
+        require!(
+            fee_override.unwrap_or(0) <= MAX_FEE_BPS,
+            MarketplaceError::InvalidFee
+        );
+
+        self.approved_collection.set_inner(ApprovedCollection {
+            collection_mint: self.collection_mint.key(),
+            fee_override,
+            bump: bumps.approved_collection,
+        });
+
+        Ok(())
+    }
+}
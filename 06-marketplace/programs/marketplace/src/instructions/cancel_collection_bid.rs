@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{CollectionBid, Marketplace};
+
+#[derive(Accounts)]
+pub struct CancelCollectionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    // Closing the bid refunds the lamports escrowed for unfilled items
+    #[account(
+      mut,
+      close = bidder,
+      has_one = bidder,
+      has_one = collection_mint,
+      seeds = [
+        b"collection_bid",
+        marketplace.key().as_ref(),
+        collection_mint.key().as_ref(),
+        bidder.key().as_ref(),
+      ],
+      bump = collection_bid.bump,
+    )]
+    pub collection_bid: Account<'info, CollectionBid>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Marketplace, Offer};
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    // Closing the offer refunds the escrowed lamports along with the rent
+    #[account(
+      mut,
+      close = bidder,
+      has_one = bidder,
+      has_one = maker_mint,
+      seeds = [
+        b"offer",
+        marketplace.key().as_ref(),
+        maker_mint.key().as_ref(),
+        bidder.key().as_ref(),
+      ],
+      bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+}
@@ -1,11 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{close_account, transfer_checked, CloseAccount, TransferChecked},
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
 };
 
-use crate::state::{Listing, Marketplace};
+use crate::{
+    error::MarketplaceError,
+    state::{Listing, Marketplace},
+};
 
 #[derive(Accounts)]
 pub struct Delist<'info> {
@@ -40,6 +45,12 @@ pub struct Delist<'info> {
         bump = listing.bump,
         has_one = maker,
         has_one = maker_mint,
+        constraint = listing
+            .auction
+            .as_ref()
+            .and_then(|auction| auction.highest_bidder)
+            .is_none()
+            @ MarketplaceError::AuctionHasBids,
         close = maker
     )]
     pub listing: Account<'info, Listing>,
@@ -52,7 +63,8 @@ pub struct Delist<'info> {
 impl Delist<'_> {
     pub fn withdraw_nft(&mut self) -> Result<()> {
         // Transfer NFT back to maker
-        let seeds: &[&[u8]; 3] = &[
+        let seeds: &[&[u8]; 4] = &[
+            b"listing",
             &self.marketplace.key().to_bytes()[..],
             &self.maker_mint.key().to_bytes()[..],
             &[self.listing.bump],
@@ -73,7 +85,8 @@ impl Delist<'_> {
     }
 
     pub fn close_mint_vault(&mut self) -> Result<()> {
-        let seeds: &[&[u8]; 3] = &[
+        let seeds: &[&[u8]; 4] = &[
+            b"listing",
             &self.marketplace.key().to_bytes()[..],
             &self.maker_mint.key().to_bytes()[..],
             &[self.listing.bump],
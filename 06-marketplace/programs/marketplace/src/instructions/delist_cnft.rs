@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    bubblegum::{
+        self, CnftTransfer, BUBBLEGUM_PROGRAM_ID, SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID,
+    },
+    state::{CnftListing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct DelistCnft<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+      mut,
+      close = maker,
+      has_one = maker,
+      has_one = merkle_tree,
+      seeds = [b"cnft_listing", marketplace.key().as_ref(), listing.asset_id.as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Account<'info, CnftListing>,
+
+    /// CHECK: Bubblegum tree authority, validated by seeds
+    #[account(
+      seeds = [merkle_tree.key().as_ref()],
+      seeds::program = bubblegum_program.key(),
+      bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: must match the listing; the leaf is validated by Bubblegum
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DelistCnft<'info> {
+    pub fn withdraw_cnft(&mut self, root: [u8; 32], proof: &[AccountInfo<'info>]) -> Result<()> {
+        let seeds: &[&[u8]; 4] = &[
+            b"cnft_listing",
+            &self.marketplace.key().to_bytes()[..],
+            &self.listing.asset_id.to_bytes()[..],
+            &[self.listing.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+        let accounts = CnftTransfer {
+            tree_config: self.tree_config.as_ref(),
+            leaf_owner: self.listing.as_ref(),
+            new_leaf_owner: self.maker.as_ref(),
+            merkle_tree: self.merkle_tree.as_ref(),
+            log_wrapper: self.log_wrapper.as_ref(),
+            compression_program: self.compression_program.as_ref(),
+            system_program: self.system_program.as_ref(),
+            bubblegum_program: self.bubblegum_program.as_ref(),
+        };
+
+        bubblegum::transfer(accounts, &self.listing.leaf(root), proof, signer_seeds)
+    }
+}
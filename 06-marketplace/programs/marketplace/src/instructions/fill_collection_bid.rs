@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    collection::is_verified_member,
+    error::MarketplaceError,
+    royalties::pay_royalties_from_escrow,
+    state::{ApprovedCollection, CollectionBid, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct FillCollectionBid<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      has_one = collection_mint,
+      seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+      bump = approved_collection.bump,
+    )]
+    pub approved_collection: Box<Account<'info, ApprovedCollection>>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      mut,
+      associated_token::mint = maker_mint,
+      associated_token::authority = maker,
+    )]
+    pub maker_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      init_if_needed,
+      payer = maker,
+      associated_token::mint = maker_mint,
+      associated_token::authority = bidder,
+    )]
+    pub bidder_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      has_one = bidder,
+      has_one = collection_mint,
+      seeds = [
+        b"collection_bid",
+        marketplace.key().as_ref(),
+        collection_mint.key().as_ref(),
+        bidder.key().as_ref(),
+      ],
+      bump = collection_bid.bump,
+    )]
+    pub collection_bid: Account<'info, CollectionBid>,
+    #[account(
+      mut,
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+      seeds = [
+        b"metadata",
+        metadata_program.key().as_ref(),
+        maker_mint.key().as_ref(),
+      ],
+      seeds::program = metadata_program.key(),
+      bump,
+      constraint = is_verified_member(&metadata, &collection_mint.key()) @ MarketplaceError::InvalidCollection,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+    #[account(
+      seeds = [
+        b"metadata",
+        metadata_program.key().as_ref(),
+        maker_mint.key().as_ref(),
+        b"edition",
+      ],
+      seeds::program = metadata_program.key(),
+      bump,
+    )]
+    pub master_edition: Box<Account<'info, MasterEditionAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>, // Metaplex program
+    pub associated_token_program: Program<'info, AssociatedToken>, // for creating associated token accounts
+    pub system_program: Program<'info, System>,                    // for creating accounts
+    pub token_program: Interface<'info, TokenInterface>,           // for token operations
+}
+
+impl<'info> FillCollectionBid<'info> {
+    // Pay one item's price out of the bid, split between creators, the treasury
+    // and the maker
+    pub fn send_sol(&mut self, creators: &[AccountInfo<'info>]) -> Result<()> {
+        let price = self.collection_bid.price;
+        let marketplace_fee = self
+            .marketplace
+            .fee_for(price, self.approved_collection.fee_override)?;
+
+        let royalties = pay_royalties_from_escrow(
+            &self.collection_bid.to_account_info(),
+            &self.metadata,
+            price,
+            creators,
+        )?;
+
+        let proceeds = price
+            .checked_sub(marketplace_fee)
+            .and_then(|proceeds| proceeds.checked_sub(royalties))
+            .ok_or(MarketplaceError::Overflow)?;
+
+        self.collection_bid.sub_lamports(marketplace_fee)?;
+        self.treasury.add_lamports(marketplace_fee)?;
+
+        self.collection_bid.sub_lamports(proceeds)?;
+        self.maker.add_lamports(proceeds)?;
+
+        Ok(())
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.maker_ata.to_account_info(),
+            mint: self.maker_mint.to_account_info(),
+            to: self.bidder_ata.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, 1, self.maker_mint.decimals)
+    }
+
+    // Count the fill and close the bid, refunding its rent, once every item is bought
+    pub fn record_fill(&mut self) -> Result<()> {
+        self.collection_bid.quantity = self
+            .collection_bid
+            .quantity
+            .checked_sub(1)
+            .ok_or(MarketplaceError::Overflow)?;
+
+        if self.collection_bid.quantity == 0 {
+            self.collection_bid.close(self.bidder.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface};
 
-use crate::state::Marketplace;
+use crate::{error::MarketplaceError, state::Marketplace, MAX_FEE_BPS};
 
 #[derive(Accounts)]
 #[instruction(name: String)]
@@ -36,7 +36,16 @@ pub struct Initialize<'info> {
 }
 
 impl Initialize<'_> {
-    pub fn init(&mut self, name: String, fee: u16, bumps: &InitializeBumps) -> Result<()> {
+    pub fn init(
+        &mut self,
+        name: String,
+        fee: u16,
+        rewards_per_sol: u64,
+        reward_maker: bool,
+        bumps: &InitializeBumps,
+    ) -> Result<()> {
+        require!(fee <= MAX_FEE_BPS, MarketplaceError::InvalidFee);
+
         self.marketplace.set_inner(
             Marketplace {
                 admin: self.admin.key(),
@@ -44,6 +53,9 @@ impl Initialize<'_> {
                 bump: bumps.marketplace,
                 treasury_bump: bumps.treasury,
                 rewards_bump: bumps.reward_mint,
+                rewards_per_sol,
+                reward_maker,
+                pending_admin: None,
                 name,
             }
         );
@@ -2,11 +2,14 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{MasterEditionAccount, Metadata, MetadataAccount},
-    token::{transfer_checked, TransferChecked},
-    token_interface::{Mint, TokenAccount, TokenInterface},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
-use crate::state::{Listing, Marketplace};
+use crate::{
+    collection::is_verified_member,
+    error::MarketplaceError,
+    state::{ApprovedCollection, EnglishAuction, Listing, Marketplace},
+};
 
 #[derive(Accounts)]
 pub struct List<'info> {
@@ -42,6 +45,14 @@ pub struct List<'info> {
     pub listing: Account<'info, Listing>,
 
     pub collection_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      has_one = collection_mint,
+      seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+      bump = approved_collection.bump,
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+    /// Optional SPL mint to price the listing in instead of lamports.
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
     #[account(
       seeds = [
         b"metadata", 
@@ -50,8 +61,7 @@ pub struct List<'info> {
       ],
       seeds::program = metadata_program.key(),
       bump,
-      constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection_mint.key().as_ref(),
-      constraint = metadata.collection.as_ref().unwrap().verified,
+      constraint = is_verified_member(&metadata, &collection_mint.key()) @ MarketplaceError::InvalidCollection,
     )]
     pub metadata: Account<'info, MetadataAccount>,
 
@@ -81,6 +91,49 @@ impl List<'_> {
             maker_mint: self.maker_mint.key(),
             price,
             bump: bumps.listing,
+            payment_mint: self.payment_mint.as_ref().map(|mint| mint.key()),
+            auction: None,
+            fee_override: self.approved_collection.fee_override,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_auction(
+        &mut self,
+        reserve_price: u64,
+        duration: i64,
+        extension: i64,
+        bumps: &ListBumps,
+    ) -> Result<()> {
+        require!(
+            duration > 0 && extension >= 0,
+            MarketplaceError::InvalidAuctionDuration
+        );
+        // Bids are escrowed in lamports
+        require!(
+            self.payment_mint.is_none(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let end_time = Clock::get()?
+            .unix_timestamp
+            .checked_add(duration)
+            .ok_or(MarketplaceError::Overflow)?;
+
+        self.listing.set_inner(Listing {
+            maker: self.maker.key(),
+            maker_mint: self.maker_mint.key(),
+            price: reserve_price,
+            bump: bumps.listing,
+            payment_mint: None,
+            auction: Some(EnglishAuction {
+                end_time,
+                extension,
+                highest_bidder: None,
+                highest_bid: 0,
+            }),
+            fee_override: self.approved_collection.fee_override,
         });
 
         Ok(())
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    bubblegum::{
+        self, CnftTransfer, LeafArgs, MetadataArgs, BUBBLEGUM_PROGRAM_ID,
+        SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID,
+    },
+    collection::is_verified_cnft_member,
+    error::MarketplaceError,
+    state::{ApprovedCollection, CnftListing, Marketplace},
+};
+
+#[derive(Accounts)]
+#[instruction(asset_id: Pubkey)]
+pub struct ListCnft<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+      init,
+      payer = maker,
+      seeds = [b"cnft_listing", marketplace.key().as_ref(), asset_id.as_ref()],
+      bump,
+      space = CnftListing::INIT_SPACE,
+    )]
+    pub listing: Account<'info, CnftListing>,
+
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      has_one = collection_mint,
+      seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+      bump = approved_collection.bump,
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+
+    /// CHECK: Bubblegum tree authority, validated by seeds
+    #[account(
+      seeds = [merkle_tree.key().as_ref()],
+      seeds::program = bubblegum_program.key(),
+      bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: validated by Bubblegum against the proof
+    #[account(mut, owner = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ListCnft<'info> {
+    pub fn create_listing(
+        &mut self,
+        asset_id: Pubkey,
+        price: u64,
+        leaf: &LeafArgs,
+        metadata: &MetadataArgs,
+        bumps: &ListCnftBumps,
+    ) -> Result<()> {
+        require!(price > 0, MarketplaceError::InvalidPrice);
+        require_keys_eq!(
+            asset_id,
+            bubblegum::asset_id(&self.merkle_tree.key(), leaf.nonce),
+            MarketplaceError::InvalidAssetId
+        );
+
+        // The metadata must hash to the leaf, which Bubblegum then proves when the
+        // NFT is deposited, so its collection can be trusted
+        require!(
+            bubblegum::hash_metadata(metadata)? == leaf.data_hash
+                && bubblegum::hash_creators(&metadata.creators) == leaf.creator_hash,
+            MarketplaceError::InvalidCnftMetadata
+        );
+        require!(
+            is_verified_cnft_member(metadata, &self.collection_mint.key()),
+            MarketplaceError::InvalidCollection
+        );
+
+        self.listing.set_inner(CnftListing {
+            maker: self.maker.key(),
+            asset_id,
+            merkle_tree: self.merkle_tree.key(),
+            price,
+            data_hash: leaf.data_hash,
+            creator_hash: leaf.creator_hash,
+            nonce: leaf.nonce,
+            index: leaf.index,
+            bump: bumps.listing,
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            fee_override: self.approved_collection.fee_override,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_cnft(&mut self, leaf: &LeafArgs, proof: &[AccountInfo<'info>]) -> Result<()> {
+        let accounts = CnftTransfer {
+            tree_config: self.tree_config.as_ref(),
+            leaf_owner: self.maker.as_ref(),
+            new_leaf_owner: self.listing.as_ref(),
+            merkle_tree: self.merkle_tree.as_ref(),
+            log_wrapper: self.log_wrapper.as_ref(),
+            compression_program: self.compression_program.as_ref(),
+            system_program: self.system_program.as_ref(),
+            bubblegum_program: self.bubblegum_program.as_ref(),
+        };
+
+        bubblegum::transfer(accounts, leaf, proof, &[])
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::MarketplaceError,
+    state::{ApprovedCollection, CollectionBid, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct MakeCollectionBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    // Fills charge the collection's fee override, so only approved collections take bids
+    #[account(
+      has_one = collection_mint,
+      seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+      bump = approved_collection.bump,
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+    #[account(
+      init,
+      payer = bidder,
+      seeds = [
+        b"collection_bid",
+        marketplace.key().as_ref(),
+        collection_mint.key().as_ref(),
+        bidder.key().as_ref(),
+      ],
+      bump,
+      space = CollectionBid::INIT_SPACE
+    )]
+    pub collection_bid: Account<'info, CollectionBid>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MakeCollectionBid<'_> {
+    pub fn make_collection_bid(
+        &mut self,
+        price: u64,
+        quantity: u32,
+        bumps: &MakeCollectionBidBumps,
+    ) -> Result<()> {
+        require!(price > 0 && quantity > 0, MarketplaceError::InvalidBid);
+
+        let total = price
+            .checked_mul(quantity as u64)
+            .ok_or(MarketplaceError::Overflow)?;
+
+        self.collection_bid.set_inner(CollectionBid {
+            bidder: self.bidder.key(),
+            collection_mint: self.collection_mint.key(),
+            price,
+            quantity,
+            bump: bumps.collection_bid,
+        });
+
+        // Escrow the lamports for every item up front
+        let cpi_accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.collection_bid.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, total)
+    }
+}
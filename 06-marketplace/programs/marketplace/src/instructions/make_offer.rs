@@ -0,0 +1,58 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::MarketplaceError,
+    state::{Marketplace, Offer},
+};
+
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      init,
+      payer = bidder,
+      seeds = [
+        b"offer",
+        marketplace.key().as_ref(),
+        maker_mint.key().as_ref(),
+        bidder.key().as_ref(),
+      ],
+      bump,
+      space = Offer::INIT_SPACE
+    )]
+    pub offer: Account<'info, Offer>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MakeOffer<'_> {
+    pub fn make_offer(&mut self, amount: u64, bumps: &MakeOfferBumps) -> Result<()> {
+        require!(amount > 0, MarketplaceError::InvalidOfferAmount);
+
+        self.offer.set_inner(Offer {
+            bidder: self.bidder.key(),
+            maker_mint: self.maker_mint.key(),
+            amount,
+            bump: bumps.offer,
+        });
+
+        // Escrow the offered lamports in the offer account
+        let cpi_accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.offer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)
+    }
+}
@@ -8,4 +8,61 @@ pub mod purchase;
 pub use purchase::*;
 
 pub mod delist;
-pub use delist::*;
\ No newline at end of file
+pub use delist::*;
+
+pub mod withdraw_treasury;
+pub use withdraw_treasury::*;
+
+pub mod withdraw_treasury_token;
+pub use withdraw_treasury_token::*;
+
+pub mod update_marketplace;
+pub use update_marketplace::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod update_price;
+pub use update_price::*;
+
+pub mod purchase_with_token;
+pub use purchase_with_token::*;
+
+pub mod make_offer;
+pub use make_offer::*;
+
+pub mod cancel_offer;
+pub use cancel_offer::*;
+
+pub mod accept_offer;
+pub use accept_offer::*;
+
+pub mod make_collection_bid;
+pub use make_collection_bid::*;
+
+pub mod cancel_collection_bid;
+pub use cancel_collection_bid::*;
+
+pub mod fill_collection_bid;
+pub use fill_collection_bid::*;
+
+pub mod place_bid;
+pub use place_bid::*;
+
+pub mod settle;
+pub use settle::*;
+
+pub mod approve_collection;
+pub use approve_collection::*;
+
+pub mod revoke_collection;
+pub use revoke_collection::*;
+
+pub mod list_cnft;
+pub use list_cnft::*;
+
+pub mod delist_cnft;
+pub use delist_cnft::*;
+
+pub mod purchase_cnft;
+pub use purchase_cnft::*;
@@ -0,0 +1,80 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::MarketplaceError,
+    state::{Listing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      mut,
+      has_one = maker_mint,
+      constraint = listing.is_auction() @ MarketplaceError::NotAnAuction,
+      seeds = [b"listing", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>,
+    // Current highest bidder, refunded when outbid
+    #[account(mut)]
+    pub previous_bidder: Option<SystemAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl PlaceBid<'_> {
+    pub fn place_bid(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let mut auction = self
+            .listing
+            .auction
+            .clone()
+            .ok_or(MarketplaceError::NotAnAuction)?;
+
+        require!(now < auction.end_time, MarketplaceError::AuctionEnded);
+        require!(
+            amount >= auction.min_bid(self.listing.price)?,
+            MarketplaceError::BidTooLow
+        );
+
+        // Escrow the new bid in the listing account
+        let cpi_accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.listing.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, amount)?;
+
+        // Refund the bid being replaced
+        if let Some(highest_bidder) = auction.highest_bidder {
+            let previous_bidder = self
+                .previous_bidder
+                .as_ref()
+                .ok_or(MarketplaceError::InvalidPreviousBidder)?;
+            require_keys_eq!(
+                previous_bidder.key(),
+                highest_bidder,
+                MarketplaceError::InvalidPreviousBidder
+            );
+
+            self.listing.sub_lamports(auction.highest_bid)?;
+            previous_bidder.add_lamports(auction.highest_bid)?;
+        }
+
+        auction.record_bid(self.bidder.key(), amount, now)?;
+        self.listing.auction = Some(auction);
+
+        Ok(())
+    }
+}
@@ -4,11 +4,17 @@ use anchor_lang::{
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{close_account, transfer_checked, CloseAccount, TransferChecked},
+    metadata::{Metadata, MetadataAccount},
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::state::{Listing, Marketplace};
+use crate::{
+    error::MarketplaceError,
+    listing_vault::{close_listing_vault, send_listed_nft},
+    rewards::mint_rewards,
+    royalties::{creator_royalties, pay_royalties},
+    state::{Listing, Marketplace},
+};
 
 #[derive(Accounts)]
 pub struct Purchase<'info> {
@@ -38,6 +44,13 @@ pub struct Purchase<'info> {
     )]
     pub taker_rewards_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+      mut,
+      associated_token::mint = reward_mint,
+      associated_token::authority = maker,
+    )]
+    pub maker_rewards_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(
       mut,
       seeds = [b"rewards", marketplace.key().as_ref()],
@@ -55,86 +68,117 @@ pub struct Purchase<'info> {
     #[account(
       mut,
       close = maker,
+      has_one = maker,
+      constraint = listing.payment_mint.is_none() @ MarketplaceError::PaymentMintMismatch,
+      constraint = !listing.is_auction() @ MarketplaceError::AuctionListing,
       seeds = [b"listing", marketplace.key().as_ref(), maker_mint.key().as_ref()],
       bump = listing.bump,
     )]
     pub listing: Account<'info, Listing>,
 
     #[account(
+      mut,
       seeds = [b"treasury", marketplace.key().as_ref()],
-      bump,
+      bump = marketplace.treasury_bump,
     )]
     pub treasury: SystemAccount<'info>,
 
+    #[account(
+      seeds = [
+        b"metadata",
+        metadata_program.key().as_ref(),
+        maker_mint.key().as_ref(),
+      ],
+      seeds::program = metadata_program.key(),
+      bump,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>, // Metaplex program
     pub associated_token_program: Program<'info, AssociatedToken>, // for creating associated token accounts
     pub system_program: Program<'info, System>,                    // for creating accounts
     pub token_program: Interface<'info, TokenInterface>,           // for token operations
 }
 
-impl Purchase<'_> {
-    pub fn send_sol(&mut self) -> Result<()> {
-        let cpi_program = self.system_program.to_account_info();
+impl<'info> Purchase<'info> {
+    // Guards the taker against the maker raising the price while the purchase is in flight
+    pub fn check_price(&self, expected_price: u64) -> Result<()> {
+        require!(
+            self.listing.price == expected_price,
+            MarketplaceError::PriceMismatch
+        );
+
+        Ok(())
+    }
+
+    pub fn send_sol(&mut self, creators: &[AccountInfo<'info>]) -> Result<()> {
+        let price = self.listing.price;
+        let marketplace_fee = self.marketplace.fee_for(price, self.listing.fee_override)?;
+        let royalties = self.pay_royalties(price, creators)?;
+        let amount = price
+            .checked_sub(marketplace_fee)
+            .and_then(|amount| amount.checked_sub(royalties))
+            .ok_or(MarketplaceError::Overflow)?;
+
+        // Pay the seller their proceeds net of the marketplace fee and royalties
         let cpi_accounts = Transfer {
             from: self.taker.to_account_info(),
-            to: self.treasury.to_account_info(),
+            to: self.maker.to_account_info(),
         };
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-
-        let marketplace_fee = (self.marketplace.fee as u64)
-            .checked_mul(self.listing.price)
-            .unwrap()
-            .checked_div(10000_u64)
-            .unwrap();
-
-        let amount = self.listing.price.checked_sub(marketplace_fee).unwrap();
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
 
         transfer(cpi_ctx, amount)?;
 
+        // Send the fee to the marketplace treasury
         let fee_accounts = Transfer {
             from: self.taker.to_account_info(),
             to: self.treasury.to_account_info(),
         };
-
         let fee_ctx = CpiContext::new(self.system_program.to_account_info(), fee_accounts);
 
         transfer(fee_ctx, marketplace_fee)
     }
 
+    // Pay each verified creator their share of the royalty and return the total
+    // paid out.
+    fn pay_royalties(&self, price: u64, creators: &[AccountInfo<'info>]) -> Result<u64> {
+        pay_royalties(
+            &self.taker.to_account_info(),
+            &self.system_program.to_account_info(),
+            creator_royalties(&self.metadata, price)?,
+            creators,
+        )
+    }
+
+    pub fn mint_rewards(&mut self) -> Result<()> {
+        mint_rewards(
+            &self.marketplace,
+            &self.reward_mint,
+            &self.taker_rewards_ata,
+            self.maker_rewards_ata.as_ref(),
+            &self.token_program,
+            self.listing.price,
+        )
+    }
+
     pub fn send_nft(&mut self) -> Result<()> {
-        let seeds: &[&[u8]; 3] = &[
-            &self.marketplace.key().to_bytes()[..],
-            &self.maker_mint.key().to_bytes()[..],
-            &[self.listing.bump],
-        ];
-
-        let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
-        let cpi_program = self.token_program.to_account_info();
-        let cpi_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            mint: self.maker_mint.to_account_info(),
-            to: self.taker_ata.to_account_info(),
-            authority: self.listing.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
-        transfer_checked(cpi_ctx, 1, self.maker_mint.decimals)
+        send_listed_nft(
+            &self.marketplace.key(),
+            &self.listing,
+            &self.maker_mint,
+            &self.vault,
+            self.taker_ata.to_account_info(),
+            &self.token_program,
+        )
     }
-    pub fn close_mint_vault(&mut self) -> Result<()> {
-        let seeds: &[&[u8]; 3] = &[
-            &self.marketplace.key().to_bytes(),
-            &self.maker_mint.key().to_bytes(),
-            &[self.listing.bump],
-        ];
-
-        let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
-
-        let cpi_program = self.token_program.to_account_info();
-        let close_accounts = CloseAccount {
-            account: self.vault.to_account_info(),
-            destination: self.maker.to_account_info(),
-            authority: self.listing.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(cpi_program, close_accounts).with_signer(signer_seeds);
 
-        close_account(cpi_ctx)
+    pub fn close_mint_vault(&mut self) -> Result<()> {
+        close_listing_vault(
+            &self.marketplace.key(),
+            &self.listing,
+            &self.vault,
+            self.maker.to_account_info(),
+            &self.token_program,
+        )
     }
 }
@@ -0,0 +1,190 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    bubblegum::{
+        self, CnftTransfer, Creator, BUBBLEGUM_PROGRAM_ID, SPL_ACCOUNT_COMPRESSION_ID,
+        SPL_NOOP_ID,
+    },
+    error::MarketplaceError,
+    rewards::mint_rewards,
+    royalties::{cnft_creator_royalties, pay_royalties},
+    state::{CnftListing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct PurchaseCnft<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+      mut,
+      close = maker,
+      has_one = maker,
+      has_one = merkle_tree,
+      seeds = [b"cnft_listing", marketplace.key().as_ref(), listing.asset_id.as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Account<'info, CnftListing>,
+    #[account(
+      mut,
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+      init_if_needed,
+      payer = taker,
+      associated_token::mint = reward_mint,
+      associated_token::authority = taker,
+    )]
+    pub taker_rewards_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      associated_token::mint = reward_mint,
+      associated_token::authority = maker,
+    )]
+    pub maker_rewards_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+      mut,
+      seeds = [b"rewards", marketplace.key().as_ref()],
+      bump = marketplace.rewards_bump,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Bubblegum tree authority, validated by seeds
+    #[account(
+      seeds = [merkle_tree.key().as_ref()],
+      seeds::program = bubblegum_program.key(),
+      bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: must match the listing; the leaf is validated by Bubblegum
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: address checked
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>, // for the reward mint
+}
+
+impl<'info> PurchaseCnft<'info> {
+    // Guards the taker against the maker raising the price while the purchase is in flight
+    pub fn check_price(&self, expected_price: u64) -> Result<()> {
+        require!(
+            self.listing.price == expected_price,
+            MarketplaceError::PriceMismatch
+        );
+
+        Ok(())
+    }
+
+    // Pay the royalties, fee and proceeds. The leaf's creators are checked against
+    // its creator hash, and the remaining accounts left after the verified creators'
+    // accounts are returned as the proof.
+    pub fn send_sol(
+        &mut self,
+        creators: &[Creator],
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<&'info [AccountInfo<'info>]> {
+        require!(
+            bubblegum::hash_creators(creators) == self.listing.creator_hash,
+            MarketplaceError::InvalidCnftMetadata
+        );
+        let verified = creators.iter().filter(|creator| creator.verified).count();
+        require!(
+            remaining_accounts.len() >= verified,
+            MarketplaceError::MissingCreatorAccount
+        );
+        let (creator_accounts, proof) = remaining_accounts.split_at(verified);
+
+        let price = self.listing.price;
+        let marketplace_fee = self.marketplace.fee_for(price, self.listing.fee_override)?;
+        let royalties = pay_royalties(
+            &self.taker.to_account_info(),
+            &self.system_program.to_account_info(),
+            cnft_creator_royalties(self.listing.seller_fee_basis_points, creators, price)?,
+            creator_accounts,
+        )?;
+        let amount = price
+            .checked_sub(marketplace_fee)
+            .and_then(|amount| amount.checked_sub(royalties))
+            .ok_or(MarketplaceError::Overflow)?;
+
+        // Pay the seller their proceeds net of the marketplace fee and royalties
+        let cpi_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: self.maker.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        // Send the fee to the marketplace treasury
+        let fee_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+        let fee_ctx = CpiContext::new(self.system_program.to_account_info(), fee_accounts);
+
+        transfer(fee_ctx, marketplace_fee)?;
+
+        Ok(proof)
+    }
+
+    pub fn mint_rewards(&mut self) -> Result<()> {
+        mint_rewards(
+            &self.marketplace,
+            &self.reward_mint,
+            &self.taker_rewards_ata,
+            self.maker_rewards_ata.as_deref(),
+            &self.token_program,
+            self.listing.price,
+        )
+    }
+
+    pub fn send_cnft(&mut self, root: [u8; 32], proof: &[AccountInfo<'info>]) -> Result<()> {
+        let seeds: &[&[u8]; 4] = &[
+            b"cnft_listing",
+            &self.marketplace.key().to_bytes()[..],
+            &self.listing.asset_id.to_bytes()[..],
+            &[self.listing.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+        let accounts = CnftTransfer {
+            tree_config: self.tree_config.as_ref(),
+            leaf_owner: self.listing.as_ref(),
+            new_leaf_owner: self.taker.as_ref(),
+            merkle_tree: self.merkle_tree.as_ref(),
+            log_wrapper: self.log_wrapper.as_ref(),
+            compression_program: self.compression_program.as_ref(),
+            system_program: self.system_program.as_ref(),
+            bubblegum_program: self.bubblegum_program.as_ref(),
+        };
+
+        bubblegum::transfer(accounts, &self.listing.leaf(root), proof, signer_seeds)
+    }
+}
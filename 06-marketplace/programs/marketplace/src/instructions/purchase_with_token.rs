@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    error::MarketplaceError,
+    listing_vault::{close_listing_vault, send_listed_nft},
+    royalties::creator_royalties,
+    state::{Listing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct PurchaseWithToken<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      init_if_needed,
+      payer = taker,
+      associated_token::mint = maker_mint,
+      associated_token::authority = taker,
+    )]
+    pub taker_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+      mut,
+      associated_token::mint = maker_mint,
+      associated_token::authority = listing,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+      mut,
+      close = maker,
+      has_one = maker,
+      constraint = listing.payment_mint == Some(payment_mint.key()) @ MarketplaceError::PaymentMintMismatch,
+      seeds = [b"listing", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mint::token_program = payment_token_program)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+      mut,
+      associated_token::mint = payment_mint,
+      associated_token::authority = taker,
+      associated_token::token_program = payment_token_program,
+    )]
+    pub taker_payment_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      init_if_needed,
+      payer = taker,
+      associated_token::mint = payment_mint,
+      associated_token::authority = maker,
+      associated_token::token_program = payment_token_program,
+    )]
+    pub maker_payment_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      init_if_needed,
+      payer = taker,
+      associated_token::mint = payment_mint,
+      associated_token::authority = treasury,
+      associated_token::token_program = payment_token_program,
+    )]
+    pub treasury_payment_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+      seeds = [
+        b"metadata",
+        metadata_program.key().as_ref(),
+        maker_mint.key().as_ref(),
+      ],
+      seeds::program = metadata_program.key(),
+      bump,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>, // Metaplex program
+    pub associated_token_program: Program<'info, AssociatedToken>, // for creating associated token accounts
+    pub system_program: Program<'info, System>,                    // for creating accounts
+    pub token_program: Interface<'info, TokenInterface>,           // for the NFT
+    pub payment_token_program: Interface<'info, TokenInterface>,   // for the payment mint
+}
+
+impl<'info> PurchaseWithToken<'info> {
+    // Guards the taker against the maker raising the price while the purchase is in flight
+    pub fn check_price(&self, expected_price: u64) -> Result<()> {
+        require!(
+            self.listing.price == expected_price,
+            MarketplaceError::PriceMismatch
+        );
+
+        Ok(())
+    }
+
+    pub fn send_payment(&mut self, creators: &'info [AccountInfo<'info>]) -> Result<()> {
+        let price = self.listing.price;
+        let marketplace_fee = self.marketplace.fee_for(price, self.listing.fee_override)?;
+        let royalties = self.pay_royalties(price, creators)?;
+        let amount = price
+            .checked_sub(marketplace_fee)
+            .and_then(|amount| amount.checked_sub(royalties))
+            .ok_or(MarketplaceError::Overflow)?;
+
+        // Pay the seller their proceeds net of the marketplace fee and royalties
+        self.transfer_payment(self.maker_payment_ata.to_account_info(), amount)?;
+
+        // Send the fee to the treasury's token account
+        self.transfer_payment(self.treasury_payment_ata.to_account_info(), marketplace_fee)
+    }
+
+    // Same split as `Purchase::pay_royalties`, except the remaining accounts are
+    // each verified creator's token account for the payment mint.
+    fn pay_royalties(&self, price: u64, creators: &'info [AccountInfo<'info>]) -> Result<u64> {
+        let mut creator_accounts = creators.iter();
+        let mut paid: u64 = 0;
+
+        for (address, share) in creator_royalties(&self.metadata, price)? {
+            let creator_account = creator_accounts
+                .next()
+                .ok_or(MarketplaceError::MissingCreatorAccount)?;
+            let creator_ata = InterfaceAccount::<TokenAccount>::try_from(creator_account)?;
+            require_keys_eq!(
+                creator_ata.owner,
+                address,
+                MarketplaceError::InvalidCreatorAccount
+            );
+            require_keys_eq!(
+                creator_ata.mint,
+                self.payment_mint.key(),
+                MarketplaceError::InvalidCreatorAccount
+            );
+
+            if share == 0 {
+                continue;
+            }
+
+            self.transfer_payment(creator_account.clone(), share)?;
+
+            paid = paid.checked_add(share).ok_or(MarketplaceError::Overflow)?;
+        }
+
+        Ok(paid)
+    }
+
+    fn transfer_payment(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            from: self.taker_payment_ata.to_account_info(),
+            mint: self.payment_mint.to_account_info(),
+            to,
+            authority: self.taker.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.payment_token_program.to_account_info(), cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {
+        send_listed_nft(
+            &self.marketplace.key(),
+            &self.listing,
+            &self.maker_mint,
+            &self.vault,
+            self.taker_ata.to_account_info(),
+            &self.token_program,
+        )
+    }
+
+    pub fn close_mint_vault(&mut self) -> Result<()> {
+        close_listing_vault(
+            &self.marketplace.key(),
+            &self.listing,
+            &self.vault,
+            self.maker.to_account_info(),
+            &self.token_program,
+        )
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::MarketplaceError,
+    state::{ApprovedCollection, Marketplace},
+};
+
+// Revoking stops new listings from the collection; existing listings remain
+#[derive(Accounts)]
+pub struct RevokeCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+      has_one = admin @ MarketplaceError::Unauthorized,
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      mut,
+      close = admin,
+      has_one = collection_mint,
+      seeds = [b"collection", marketplace.key().as_ref(), collection_mint.key().as_ref()],
+      bump = approved_collection.bump,
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+}
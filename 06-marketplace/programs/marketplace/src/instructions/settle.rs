@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::MarketplaceError,
+    listing_vault::{close_listing_vault, send_listed_nft},
+    royalties::pay_royalties_from_escrow,
+    state::{Listing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    // Anyone can settle an ended auction
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    // The winning bidder, or the maker when the auction got no bids
+    pub recipient: SystemAccount<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      init_if_needed,
+      payer = payer,
+      associated_token::mint = maker_mint,
+      associated_token::authority = recipient,
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      associated_token::mint = maker_mint,
+      associated_token::authority = listing,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      close = maker,
+      has_one = maker,
+      constraint = listing.is_auction() @ MarketplaceError::NotAnAuction,
+      seeds = [b"listing", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>,
+    #[account(
+      mut,
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+      seeds = [
+        b"metadata",
+        metadata_program.key().as_ref(),
+        maker_mint.key().as_ref(),
+      ],
+      seeds::program = metadata_program.key(),
+      bump,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+
+    pub metadata_program: Program<'info, Metadata>, // Metaplex program
+    pub associated_token_program: Program<'info, AssociatedToken>, // for creating associated token accounts
+    pub system_program: Program<'info, System>,                    // for creating accounts
+    pub token_program: Interface<'info, TokenInterface>,           // for token operations
+}
+
+impl<'info> Settle<'info> {
+    // Pay out the winning bid, split between creators, the treasury and the maker
+    pub fn send_sol(&mut self, creators: &[AccountInfo<'info>]) -> Result<()> {
+        let auction = self
+            .listing
+            .auction
+            .clone()
+            .ok_or(MarketplaceError::NotAnAuction)?;
+
+        require!(
+            Clock::get()?.unix_timestamp >= auction.end_time,
+            MarketplaceError::AuctionNotEnded
+        );
+        require_keys_eq!(
+            self.recipient.key(),
+            auction.highest_bidder.unwrap_or(self.maker.key()),
+            MarketplaceError::InvalidAuctionRecipient
+        );
+
+        if auction.highest_bidder.is_none() {
+            return Ok(());
+        }
+
+        let amount = auction.highest_bid;
+        let marketplace_fee = self
+            .marketplace
+            .fee_for(amount, self.listing.fee_override)?;
+
+        let royalties = pay_royalties_from_escrow(
+            &self.listing.to_account_info(),
+            &self.metadata,
+            amount,
+            creators,
+        )?;
+
+        let proceeds = amount
+            .checked_sub(marketplace_fee)
+            .and_then(|proceeds| proceeds.checked_sub(royalties))
+            .ok_or(MarketplaceError::Overflow)?;
+
+        self.listing.sub_lamports(marketplace_fee)?;
+        self.treasury.add_lamports(marketplace_fee)?;
+
+        self.listing.sub_lamports(proceeds)?;
+        self.maker.add_lamports(proceeds)?;
+
+        Ok(())
+    }
+
+    pub fn send_nft(&mut self) -> Result<()> {
+        send_listed_nft(
+            &self.marketplace.key(),
+            &self.listing,
+            &self.maker_mint,
+            &self.vault,
+            self.recipient_ata.to_account_info(),
+            &self.token_program,
+        )
+    }
+
+    pub fn close_mint_vault(&mut self) -> Result<()> {
+        close_listing_vault(
+            &self.marketplace.key(),
+            &self.listing,
+            &self.vault,
+            self.maker.to_account_info(),
+            &self.token_program,
+        )
+    }
+}
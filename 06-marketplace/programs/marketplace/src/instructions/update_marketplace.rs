@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarketplaceError, state::Marketplace, MAX_FEE_BPS};
+
+#[derive(Accounts)]
+pub struct UpdateMarketplace<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+      mut,
+      has_one = admin @ MarketplaceError::Unauthorized,
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+impl UpdateMarketplace<'_> {
+    pub fn update_fee(&mut self, fee: u16) -> Result<()> {
+        require!(fee <= MAX_FEE_BPS, MarketplaceError::InvalidFee);
+
+        self.marketplace.fee = fee;
+
+        Ok(())
+    }
+
+    pub fn update_rewards(&mut self, rewards_per_sol: u64) -> Result<()> {
+        self.marketplace.rewards_per_sol = rewards_per_sol;
+
+        Ok(())
+    }
+
+    // The role only moves once the new admin accepts, so a mistyped key can't lock the marketplace
+    pub fn transfer_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        require!(
+            new_admin != Pubkey::default() && new_admin != self.marketplace.admin,
+            MarketplaceError::InvalidNewAdmin
+        );
+
+        self.marketplace.pending_admin = Some(new_admin);
+
+        Ok(())
+    }
+}
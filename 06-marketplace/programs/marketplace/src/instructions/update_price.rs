@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::MarketplaceError,
+    events::PriceUpdated,
+    state::{Listing, Marketplace},
+};
+
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+      mut,
+      has_one = maker,
+      has_one = maker_mint,
+      constraint = !listing.is_auction() @ MarketplaceError::AuctionListing,
+      seeds = [b"listing", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+      bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>,
+}
+
+impl UpdatePrice<'_> {
+    pub fn update_price(&mut self, price: u64) -> Result<()> {
+        require!(price > 0, MarketplaceError::InvalidPrice);
+
+        let old_price = self.listing.price;
+        self.listing.price = price;
+
+        emit!(PriceUpdated {
+            listing: self.listing.key(),
+            maker_mint: self.maker_mint.key(),
+            old_price,
+            new_price: price,
+        });
+
+        Ok(())
+    }
+}
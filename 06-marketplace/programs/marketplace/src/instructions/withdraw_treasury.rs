@@ -0,0 +1,58 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{error::MarketplaceError, state::Marketplace};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+      has_one = admin @ MarketplaceError::Unauthorized,
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+      mut,
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl WithdrawTreasury<'_> {
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, MarketplaceError::InvalidWithdrawAmount);
+        require!(
+            amount <= self.treasury.lamports(),
+            MarketplaceError::InsufficientTreasuryFunds
+        );
+        // The treasury is either emptied or kept rent exempt, never left with dust
+        let remaining = self.treasury.lamports() - amount;
+        require!(
+            remaining == 0 || remaining >= Rent::get()?.minimum_balance(0),
+            MarketplaceError::TreasuryBelowRent
+        );
+
+        let marketplace_key = self.marketplace.key();
+        let seeds: &[&[u8]; 3] = &[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ];
+        let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: self.treasury.to_account_info(),
+            to: self.admin.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts)
+            .with_signer(signer_seeds);
+
+        transfer(cpi_ctx, amount)
+    }
+}
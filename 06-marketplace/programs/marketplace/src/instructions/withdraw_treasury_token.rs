@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::MarketplaceError, state::Marketplace};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryToken<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+      has_one = admin @ MarketplaceError::Unauthorized,
+      seeds = [b"marketplace", marketplace.name.as_bytes()],
+      bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+      seeds = [b"treasury", marketplace.key().as_ref()],
+      bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    // Fees from token sales collect in the treasury's ATA for the payment mint
+    #[account(
+      mut,
+      associated_token::mint = payment_mint,
+      associated_token::authority = treasury,
+      associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+      init_if_needed,
+      payer = admin,
+      associated_token::mint = payment_mint,
+      associated_token::authority = admin,
+      associated_token::token_program = token_program,
+    )]
+    pub admin_payment_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl WithdrawTreasuryToken<'_> {
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, MarketplaceError::InvalidWithdrawAmount);
+        require!(
+            amount <= self.treasury_payment_ata.amount,
+            MarketplaceError::InsufficientTreasuryFunds
+        );
+
+        let marketplace_key = self.marketplace.key();
+        let seeds: &[&[u8]; 3] = &[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ];
+        let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.treasury_payment_ata.to_account_info(),
+            mint: self.payment_mint.to_account_info(),
+            to: self.admin_payment_ata.to_account_info(),
+            authority: self.treasury.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts)
+            .with_signer(signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
+    }
+}
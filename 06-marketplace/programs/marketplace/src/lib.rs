@@ -1,6 +1,12 @@
+pub mod bubblegum;
+pub mod collection;
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod listing_vault;
+pub mod rewards;
+pub mod royalties;
 pub mod state;
 
 use anchor_lang::prelude::*;
@@ -15,16 +21,50 @@ declare_id!("77Nqr3YcueeRW3aVFziBEHnYcx6ZrerP6FNdJvHc2bU1");
 pub mod marketplace {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, name: String, fee: u16) -> Result<()> {
-        ctx.accounts.init(name, fee, &ctx.bumps)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        name: String,
+        fee: u16,
+        rewards_per_sol: u64,
+        reward_maker: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .init(name, fee, rewards_per_sol, reward_maker, &ctx.bumps)
     }
     pub fn list(ctx: Context<List>, price: u64) -> Result<()> {
         ctx.accounts.create_listing(price, &ctx.bumps)?;
         ctx.accounts.deposit_nft()
     }
 
-    pub fn purchase(ctx: Context<Purchase>) -> Result<()> {
-        ctx.accounts.send_sol()?;
+    pub fn list_auction(
+        ctx: Context<List>,
+        reserve_price: u64,
+        duration: i64,
+        extension: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_auction(reserve_price, duration, extension, &ctx.bumps)?;
+        ctx.accounts.deposit_nft()
+    }
+
+    pub fn purchase<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Purchase<'info>>,
+        expected_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.check_price(expected_price)?;
+        ctx.accounts.send_sol(ctx.remaining_accounts)?;
+        ctx.accounts.mint_rewards()?;
+        ctx.accounts.send_nft()?;
+        ctx.accounts.close_mint_vault()
+    }
+
+    // Rewards are rated per SOL, so sales priced in an SPL token earn none
+    pub fn purchase_with_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PurchaseWithToken<'info>>,
+        expected_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.check_price(expected_price)?;
+        ctx.accounts.send_payment(ctx.remaining_accounts)?;
         ctx.accounts.send_nft()?;
         ctx.accounts.close_mint_vault()
     }
@@ -33,4 +73,124 @@ pub mod marketplace {
         ctx.accounts.withdraw_nft()?;
         ctx.accounts.close_mint_vault()
     }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount)
+    }
+
+    pub fn withdraw_treasury_token(
+        ctx: Context<WithdrawTreasuryToken>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw(amount)
+    }
+
+    pub fn update_fee(ctx: Context<UpdateMarketplace>, fee: u16) -> Result<()> {
+        ctx.accounts.update_fee(fee)
+    }
+
+    pub fn update_rewards(ctx: Context<UpdateMarketplace>, rewards_per_sol: u64) -> Result<()> {
+        ctx.accounts.update_rewards(rewards_per_sol)
+    }
+
+    pub fn transfer_admin(ctx: Context<UpdateMarketplace>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.transfer_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn update_price(ctx: Context<UpdatePrice>, price: u64) -> Result<()> {
+        ctx.accounts.update_price(price)
+    }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, amount: u64) -> Result<()> {
+        ctx.accounts.make_offer(amount, &ctx.bumps)
+    }
+
+    pub fn cancel_offer(_ctx: Context<CancelOffer>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn accept_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcceptOffer<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.send_sol(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft()
+    }
+
+    pub fn make_collection_bid(
+        ctx: Context<MakeCollectionBid>,
+        price: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        ctx.accounts
+            .make_collection_bid(price, quantity, &ctx.bumps)
+    }
+
+    pub fn cancel_collection_bid(_ctx: Context<CancelCollectionBid>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn fill_collection_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillCollectionBid<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.send_sol(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft()?;
+        ctx.accounts.record_fill()
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.place_bid(amount)
+    }
+
+    pub fn settle<'info>(ctx: Context<'_, '_, 'info, 'info, Settle<'info>>) -> Result<()> {
+        ctx.accounts.send_sol(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft()?;
+        ctx.accounts.close_mint_vault()
+    }
+
+    pub fn approve_collection(
+        ctx: Context<ApproveCollection>,
+        fee_override: Option<u16>,
+    ) -> Result<()> {
+        ctx.accounts.approve(fee_override, &ctx.bumps)
+    }
+
+    pub fn revoke_collection(_ctx: Context<RevokeCollection>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn list_cnft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ListCnft<'info>>,
+        asset_id: Pubkey,
+        price: u64,
+        leaf: bubblegum::LeafArgs,
+        metadata: bubblegum::MetadataArgs,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_listing(asset_id, price, &leaf, &metadata, &ctx.bumps)?;
+        ctx.accounts.deposit_cnft(&leaf, ctx.remaining_accounts)
+    }
+
+    pub fn delist_cnft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DelistCnft<'info>>,
+        root: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.withdraw_cnft(root, ctx.remaining_accounts)
+    }
+
+    // Remaining accounts are the leaf's verified creators, then the proof nodes
+    pub fn purchase_cnft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PurchaseCnft<'info>>,
+        expected_price: u64,
+        root: [u8; 32],
+        creators: Vec<bubblegum::Creator>,
+    ) -> Result<()> {
+        ctx.accounts.check_price(expected_price)?;
+        let proof = ctx.accounts.send_sol(&creators, ctx.remaining_accounts)?;
+        ctx.accounts.mint_rewards()?;
+        ctx.accounts.send_cnft(root, proof)
+    }
 }
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::state::Listing;
+
+// Send the listed NFT out of the vault, signed by the listing PDA
+pub fn send_listed_nft<'info>(
+    marketplace: &Pubkey,
+    listing: &Account<'info, Listing>,
+    maker_mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let maker_mint_key = maker_mint.key();
+    let seeds: &[&[u8]; 4] = &[
+        b"listing",
+        marketplace.as_ref(),
+        maker_mint_key.as_ref(),
+        &[listing.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        mint: maker_mint.to_account_info(),
+        to,
+        authority: listing.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new(token_program.to_account_info(), cpi_accounts).with_signer(signer_seeds);
+    transfer_checked(cpi_ctx, 1, maker_mint.decimals)
+}
+
+// Close the emptied vault, returning its rent to destination
+pub fn close_listing_vault<'info>(
+    marketplace: &Pubkey,
+    listing: &Account<'info, Listing>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let seeds: &[&[u8]; 4] = &[
+        b"listing",
+        marketplace.as_ref(),
+        listing.maker_mint.as_ref(),
+        &[listing.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+    let close_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination,
+        authority: listing.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new(token_program.to_account_info(), close_accounts).with_signer(signer_seeds);
+    close_account(cpi_ctx)
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::state::Marketplace;
+
+// Mint the rewards for a SOL sale to the buyer, and to the seller when enabled
+pub fn mint_rewards<'info>(
+    marketplace: &Account<'info, Marketplace>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    taker_rewards_ata: &InterfaceAccount<'info, TokenAccount>,
+    maker_rewards_ata: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    price: u64,
+) -> Result<()> {
+    let rewards = marketplace.rewards_for(price)?;
+    if rewards == 0 {
+        return Ok(());
+    }
+
+    let seeds: &[&[u8]; 3] = &[
+        b"marketplace",
+        marketplace.name.as_bytes(),
+        &[marketplace.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+    // Reward the buyer
+    let cpi_accounts = MintTo {
+        mint: reward_mint.to_account_info(),
+        to: taker_rewards_ata.to_account_info(),
+        authority: marketplace.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new(token_program.to_account_info(), cpi_accounts).with_signer(signer_seeds);
+    mint_to(cpi_ctx, rewards)?;
+
+    // A seller without a rewards account is skipped rather than reverting the purchase
+    let Some(maker_rewards_ata) = maker_rewards_ata.filter(|_| marketplace.reward_maker) else {
+        return Ok(());
+    };
+    let cpi_accounts = MintTo {
+        mint: reward_mint.to_account_info(),
+        to: maker_rewards_ata.to_account_info(),
+        authority: marketplace.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new(token_program.to_account_info(), cpi_accounts).with_signer(signer_seeds);
+    mint_to(cpi_ctx, rewards)
+}
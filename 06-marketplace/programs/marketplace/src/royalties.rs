@@ -0,0 +1,121 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::{bubblegum::Creator, error::MarketplaceError, MAX_FEE_BPS};
+
+// Royalty owed to each verified creator, in metadata order. Callers pass the
+// creator accounts as remaining accounts in the same order
+pub fn creator_royalties(metadata: &MetadataAccount, price: u64) -> Result<Vec<(Pubkey, u64)>> {
+    let creators = metadata
+        .creators
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter(|creator| creator.verified)
+        .map(|creator| (creator.address, creator.share));
+
+    royalty_shares(metadata.seller_fee_basis_points, creators, price)
+}
+
+// Same as creator_royalties, for a compressed NFT's creators
+pub fn cnft_creator_royalties(
+    seller_fee_basis_points: u16,
+    creators: &[Creator],
+    price: u64,
+) -> Result<Vec<(Pubkey, u64)>> {
+    let creators = creators
+        .iter()
+        .filter(|creator| creator.verified)
+        .map(|creator| (creator.address, creator.share));
+
+    royalty_shares(seller_fee_basis_points, creators, price)
+}
+
+fn royalty_shares(
+    seller_fee_basis_points: u16,
+    creators: impl Iterator<Item = (Pubkey, u8)>,
+    price: u64,
+) -> Result<Vec<(Pubkey, u64)>> {
+    let royalty = (seller_fee_basis_points as u64)
+        .checked_mul(price)
+        .ok_or(MarketplaceError::Overflow)?
+        / MAX_FEE_BPS as u64;
+
+    creators
+        .map(|(address, share)| {
+            royalty
+                .checked_mul(share as u64)
+                .map(|share| (address, share / 100))
+                .ok_or(MarketplaceError::Overflow.into())
+        })
+        .collect()
+}
+
+// Pay each creator from the payer, returns the total paid out
+pub fn pay_royalties<'info>(
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    royalties: Vec<(Pubkey, u64)>,
+    creators: &[AccountInfo<'info>],
+) -> Result<u64> {
+    let mut creator_accounts = creators.iter();
+    let mut paid: u64 = 0;
+
+    for (address, share) in royalties {
+        let creator_account = creator_accounts
+            .next()
+            .ok_or(MarketplaceError::MissingCreatorAccount)?;
+        require_keys_eq!(
+            creator_account.key(),
+            address,
+            MarketplaceError::InvalidCreatorAccount
+        );
+
+        if share == 0 {
+            continue;
+        }
+
+        let cpi_accounts = Transfer {
+            from: payer.clone(),
+            to: creator_account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+        transfer(cpi_ctx, share)?;
+
+        paid = paid.checked_add(share).ok_or(MarketplaceError::Overflow)?;
+    }
+
+    Ok(paid)
+}
+
+// Pay each creator out of a program-owned escrow, returns the total paid out
+pub fn pay_royalties_from_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    metadata: &MetadataAccount,
+    price: u64,
+    creators: &[AccountInfo<'info>],
+) -> Result<u64> {
+    let mut creator_accounts = creators.iter();
+    let mut paid: u64 = 0;
+
+    for (address, share) in creator_royalties(metadata, price)? {
+        let creator_account = creator_accounts
+            .next()
+            .ok_or(MarketplaceError::MissingCreatorAccount)?;
+        require_keys_eq!(
+            creator_account.key(),
+            address,
+            MarketplaceError::InvalidCreatorAccount
+        );
+
+        escrow.sub_lamports(share)?;
+        creator_account.add_lamports(share)?;
+
+        paid = paid.checked_add(share).ok_or(MarketplaceError::Overflow)?;
+    }
+
+    Ok(paid)
+}
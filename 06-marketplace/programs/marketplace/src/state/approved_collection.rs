@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Marks a collection as listable on the marketplace.
+#[account]
+pub struct ApprovedCollection {
+    pub collection_mint: Pubkey,
+    /// Fee charged on this collection's listings instead of the marketplace fee.
+    pub fee_override: Option<u16>,
+    pub bump: u8,
+}
+impl Space for ApprovedCollection {
+    const INIT_SPACE: usize = 8 + 32 + (1 + 2) + 1;
+}
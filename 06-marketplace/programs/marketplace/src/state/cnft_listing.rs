@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::bubblegum::LeafArgs;
+
+/// A compressed NFT held by the listing PDA, keyed by its Bubblegum asset id.
+#[account]
+pub struct CnftListing {
+    pub maker: Pubkey,
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub price: u64,
+    /// Leaf fields needed to move the NFT back out of escrow.
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub bump: u8,
+    /// Royalty rate from the leaf's metadata, checked against `data_hash` at listing.
+    pub seller_fee_basis_points: u16,
+    /// Collection fee override in effect when the NFT was listed.
+    pub fee_override: Option<u16>,
+}
+impl Space for CnftListing {
+    const INIT_SPACE: usize = 8 + 32*3 + 8 + 32*2 + 8 + 4 + 1 + 2 + (1 + 2);
+}
+
+impl CnftListing {
+    /// The escrowed leaf, proven against the tree's current `root`.
+    pub fn leaf(&self, root: [u8; 32]) -> LeafArgs {
+        LeafArgs {
+            root,
+            data_hash: self.data_hash,
+            creator_hash: self.creator_hash,
+            nonce: self.nonce,
+            index: self.index,
+        }
+    }
+}
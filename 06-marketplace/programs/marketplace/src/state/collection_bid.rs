@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// A standing bid of `price` lamports each for up to `quantity` NFTs from a
+/// collection. The lamports for every unfilled item are held in this account on
+/// top of its rent.
+#[account]
+pub struct CollectionBid {
+    pub bidder: Pubkey,
+    pub collection_mint: Pubkey,
+    pub price: u64,
+    pub quantity: u32,
+    pub bump: u8,
+}
+impl Space for CollectionBid {
+    const INIT_SPACE: usize = 8 + 32*2 + 8 + 4 + 1;
+}
@@ -1,12 +1,66 @@
 use anchor_lang::prelude::*;
 
+use crate::error::MarketplaceError;
+
 #[account]
 pub struct Listing {
     pub maker: Pubkey,
     pub maker_mint: Pubkey,
     pub price: u64,
     pub bump: u8,
+    /// SPL mint the price is denominated in, or `None` for lamports.
+    pub payment_mint: Option<Pubkey>,
+    /// Set for English auctions, in which case `price` is the reserve.
+    pub auction: Option<EnglishAuction>,
+    /// Collection fee override in effect when the NFT was listed.
+    pub fee_override: Option<u16>,
 }
 impl Space for Listing {
-    const INIT_SPACE: usize = 8 + 32*2 + 8 + 1;
-}
\ No newline at end of file
+    const INIT_SPACE: usize =
+        8 + 32*2 + 8 + 1 + (1 + 32) + (1 + 8*2 + (1 + 32) + 8) + (1 + 2);
+}
+
+impl Listing {
+    // Fixed-price actions must not touch a live auction, whose vault and
+    // escrowed bid belong to the auction until it is settled
+    pub fn is_auction(&self) -> bool {
+        self.auction.is_some()
+    }
+}
+
+/// Ascending SOL auction. The highest bid is escrowed in the listing account
+/// on top of its rent.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EnglishAuction {
+    pub end_time: i64,
+    /// Bids landing within this many seconds of the end push the end out to
+    /// `extension` seconds after the bid.
+    pub extension: i64,
+    pub highest_bidder: Option<Pubkey>,
+    pub highest_bid: u64,
+}
+
+impl EnglishAuction {
+    // The first bid must meet the reserve, later bids must beat the highest bid
+    pub fn min_bid(&self, reserve: u64) -> Result<u64> {
+        match self.highest_bidder {
+            Some(_) => self
+                .highest_bid
+                .checked_add(1)
+                .ok_or(MarketplaceError::Overflow.into()),
+            None => Ok(reserve),
+        }
+    }
+
+    pub fn record_bid(&mut self, bidder: Pubkey, amount: u64, now: i64) -> Result<()> {
+        let extended_end = now
+            .checked_add(self.extension)
+            .ok_or(MarketplaceError::Overflow)?;
+
+        self.highest_bidder = Some(bidder);
+        self.highest_bid = amount;
+        self.end_time = self.end_time.max(extended_end);
+
+        Ok(())
+    }
+}
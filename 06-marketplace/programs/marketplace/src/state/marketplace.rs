@@ -1,4 +1,6 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+
+use crate::{error::MarketplaceError, MAX_FEE_BPS};
 
 #[account]
 pub struct Marketplace {
@@ -7,8 +9,35 @@ pub struct Marketplace {
     pub bump: u8,
     pub treasury_bump: u8,
     pub rewards_bump: u8,
-    pub name: String, 
+    /// Reward token base units minted per SOL spent on a purchase.
+    pub rewards_per_sol: u64,
+    /// Whether the maker is rewarded alongside the buyer.
+    pub reward_maker: bool,
+    /// Admin nominated by `transfer_admin`, who takes over once they accept.
+    pub pending_admin: Option<Pubkey>,
+    pub name: String,
 }
 impl Space for Marketplace {
-    const INIT_SPACE: usize = 8 + 32 + 2 + 1*3 + (4 + 32);
-}
\ No newline at end of file
+    const INIT_SPACE: usize = 8 + 32 + 2 + 1*3 + 8 + 1 + (1 + 32) + (4 + 32);
+}
+
+impl Marketplace {
+    /// Marketplace cut of a sale at `price`, rounded down. `fee_override` replaces
+    /// the marketplace fee for listings from collections that set one.
+    pub fn fee_for(&self, price: u64, fee_override: Option<u16>) -> Result<u64> {
+        (fee_override.unwrap_or(self.fee) as u64)
+            .checked_mul(price)
+            .map(|fee| fee / MAX_FEE_BPS as u64)
+            .ok_or(MarketplaceError::Overflow.into())
+    }
+
+    /// Reward tokens earned for a sale at `price`, rounded down. Rewards that
+    /// don't fit in a u64 fail the purchase instead of minting a capped amount.
+    pub fn rewards_for(&self, price: u64) -> Result<u64> {
+        let rewards = (self.rewards_per_sol as u128)
+            .checked_mul(price as u128)
+            .ok_or(MarketplaceError::Overflow)?
+            / LAMPORTS_PER_SOL as u128;
+        u64::try_from(rewards).map_err(|_| MarketplaceError::Overflow.into())
+    }
+}
@@ -2,4 +2,16 @@ pub mod marketplace;
 pub use marketplace::*;
 
 pub mod listing;
-pub use listing::*;
\ No newline at end of file
+pub use listing::*;
+
+pub mod offer;
+pub use offer::*;
+
+pub mod collection_bid;
+pub use collection_bid::*;
+
+pub mod approved_collection;
+pub use approved_collection::*;
+
+pub mod cnft_listing;
+pub use cnft_listing::*;
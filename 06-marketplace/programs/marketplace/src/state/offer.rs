@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// A SOL bid on a single NFT. The offered lamports are held in this account on
+/// top of its rent until the offer is accepted or cancelled.
+#[account]
+pub struct Offer {
+    pub bidder: Pubkey,
+    pub maker_mint: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+impl Space for Offer {
+    const INIT_SPACE: usize = 8 + 32*2 + 8 + 1;
+}
@@ -11,11 +11,16 @@ import {
 import {
   MINT_SIZE,
   TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountIdempotentInstruction,
   createInitializeMint2Instruction,
+  createMint,
   createMintToInstruction,
+  getAccount,
   getAssociatedTokenAddressSync,
   getMinimumBalanceForRentExemptMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
 } from "@solana/spl-token";
 import {
   PROGRAM_ID as METADATA_PROGRAM_ID,
@@ -24,6 +29,25 @@ import {
   createVerifyCollectionInstruction,
   DataV2,
 } from "@metaplex-foundation/mpl-token-metadata";
+import {
+  PROGRAM_ID as BUBBLEGUM_PROGRAM_ID,
+  MetadataArgs,
+  TokenProgramVersion,
+  TokenStandard,
+  computeCreatorHash,
+  computeDataHash,
+  createCreateTreeInstruction,
+  createMintToCollectionV1Instruction,
+  getLeafAssetId,
+} from "@metaplex-foundation/mpl-bubblegum";
+import {
+  ConcurrentMerkleTreeAccount,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
+  createAllocTreeIx,
+  emptyNode,
+} from "@solana/spl-account-compression";
+import { expect } from "chai";
 
 describe("marketplace", () => {
   // Configure the client
@@ -45,10 +69,13 @@ describe("marketplace", () => {
   const taker = Keypair.generate();
   const collectionMint = Keypair.generate();
   const nftMint = Keypair.generate();
+  const creator = Keypair.generate();
 
   // Marketplace constants
   const marketplaceName = "Test Marketplace";
   const marketplaceFee = 250; // 2.5%
+  const royaltyBasisPoints = 500; // 5%
+  const rewardsPerSol = new anchor.BN(1_000_000); // One reward token per SOL
 
   // Find PDAs
   const [marketplace] = PublicKey.findProgramAddressSync(
@@ -104,6 +131,13 @@ describe("marketplace", () => {
     TOKEN_2022_PROGRAM_ID,
   );
 
+  const makerRewardsAta = getAssociatedTokenAddressSync(
+    rewardMint,
+    maker.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID,
+  );
+
   // Find metadata PDAs
   const [collectionMetadata] = PublicKey.findProgramAddressSync(
     [
@@ -173,8 +207,8 @@ describe("marketplace", () => {
       name: "Test NFT",
       symbol: "TEST",
       uri: "https://test.uri/nft.json",
-      sellerFeeBasisPoints: 0,
-      creators: null,
+      sellerFeeBasisPoints: royaltyBasisPoints,
+      creators: [{ address: creator.publicKey, verified: true, share: 100 }],
       collection: {
         key: collectionMint.publicKey,
         verified: false,
@@ -253,7 +287,7 @@ describe("marketplace", () => {
           mint: nftMint.publicKey,
           mintAuthority: maker.publicKey,
           payer: maker.publicKey,
-          updateAuthority: maker.publicKey,
+          updateAuthority: creator.publicKey,
         },
         {
           createMetadataAccountArgsV3: {
@@ -267,7 +301,7 @@ describe("marketplace", () => {
         {
           edition: nftMasterEdition,
           mint: nftMint.publicKey,
-          updateAuthority: maker.publicKey,
+          updateAuthority: creator.publicKey,
           mintAuthority: maker.publicKey,
           payer: maker.publicKey,
           metadata: nftMetadata,
@@ -306,12 +340,14 @@ describe("marketplace", () => {
     await provider
       .sendAndConfirm(collectionTx, [admin, collectionMint])
       .then(log);
-    await provider.sendAndConfirm(nftTx, [maker, nftMint, admin]).then(log);
+    await provider
+      .sendAndConfirm(nftTx, [maker, nftMint, admin, creator])
+      .then(log);
   });
 
   it("Initialize marketplace", async () => {
     await program.methods
-      .initialize(marketplaceName, marketplaceFee)
+      .initialize(marketplaceName, marketplaceFee, rewardsPerSol, true)
       .accounts({
         admin: admin.publicKey,
         marketplace,
@@ -325,6 +361,19 @@ describe("marketplace", () => {
       .then(log);
   });
 
+  it("Approve the collection for listing", async () => {
+    await program.methods
+      .approveCollection(null)
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+  });
+
   it("List NFT", async () => {
     const price = new anchor.BN(1 * LAMPORTS_PER_SOL);
 
@@ -366,7 +415,7 @@ describe("marketplace", () => {
   });
 
   it("List NFT again", async () => {
-    const price = new anchor.BN(1.5 * LAMPORTS_PER_SOL);
+    const price = new anchor.BN(1 * LAMPORTS_PER_SOL);
 
     await program.methods
       .list(price)
@@ -389,16 +438,1604 @@ describe("marketplace", () => {
       .then(log);
   });
 
+  it("Rejects price updates from anyone but the maker", async () => {
+    try {
+      await program.methods
+        .updatePrice(new anchor.BN(1))
+        .accountsPartial({
+          maker: taker.publicKey,
+          marketplace,
+          makerMint: nftMint.publicKey,
+          listing,
+        })
+        .signers([taker])
+        .rpc();
+      expect.fail("Non-maker price update should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+  });
+
+  it("Updates the listing price and emits an event", async () => {
+    const newPrice = new anchor.BN(1.5 * LAMPORTS_PER_SOL);
+
+    let event;
+    const listener = program.addEventListener("priceUpdated", (e) => {
+      event = e;
+    });
+
+    await program.methods
+      .updatePrice(newPrice)
+      .accountsPartial({
+        maker: maker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        listing,
+      })
+      .signers([maker])
+      .rpc({ commitment: "confirmed" })
+      .then(log);
+
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    const listingAccount = await program.account.listing.fetch(listing);
+    expect(listingAccount.price.toString()).to.equal(newPrice.toString());
+    expect(event.oldPrice.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    expect(event.newPrice.toString()).to.equal(newPrice.toString());
+  });
+
+  it("Rejects a fee above 100%", async () => {
+    const name = "Greedy Marketplace";
+    const [greedyMarketplace] = PublicKey.findProgramAddressSync(
+      [Buffer.from("marketplace"), Buffer.from(name)],
+      program.programId,
+    );
+
+    try {
+      await program.methods
+        .initialize(name, 10_001, rewardsPerSol, false)
+        .accountsPartial({
+          admin: admin.publicKey,
+          marketplace: greedyMarketplace,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Initialize with fee above 10000 bps should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidFee");
+    }
+  });
+
+  it("Rejects a purchase at a price the maker has since changed", async () => {
+    try {
+      await program.methods
+        .purchase(new anchor.BN(LAMPORTS_PER_SOL))
+        .accountsPartial({
+          taker: taker.publicKey,
+          maker: maker.publicKey,
+          marketplace,
+          makerMint: nftMint.publicKey,
+          treasury,
+          metadata: nftMetadata,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([taker])
+        .rpc();
+      expect.fail("Purchase at the old price should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("PriceMismatch");
+    }
+  });
+
+  it("Rejects a purchase that skips the creator royalty", async () => {
+    try {
+      await program.methods
+        .purchase(new anchor.BN(1.5 * LAMPORTS_PER_SOL))
+        .accountsPartial({
+          taker: taker.publicKey,
+          maker: maker.publicKey,
+          marketplace,
+          makerMint: nftMint.publicKey,
+          treasury,
+          metadata: nftMetadata,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([taker])
+        .rpc();
+      expect.fail("Purchase without the creator account should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("MissingCreatorAccount");
+    }
+  });
+
   it("Purchase NFT", async () => {
+    const price = 1.5 * LAMPORTS_PER_SOL;
+    const fee = (price * marketplaceFee) / 10_000;
+    const royalty = (price * royaltyBasisPoints) / 10_000;
+    const treasuryBalanceBefore =
+      await provider.connection.getBalance(treasury);
+    const listingRent = await provider.connection.getBalance(listing);
+    const vaultRent = await provider.connection.getBalance(vault);
+
+    // Seller rewards are minted only to an existing rewards ATA, so the maker
+    // creates theirs before the sale
+    await provider
+      .sendAndConfirm(
+        new Transaction().add(
+          createAssociatedTokenAccountIdempotentInstruction(
+            maker.publicKey,
+            makerRewardsAta,
+            maker.publicKey,
+            rewardMint,
+            TOKEN_2022_PROGRAM_ID,
+          ),
+        ),
+        [maker],
+      )
+      .then(log);
+    const makerBalanceBefore = await provider.connection.getBalance(
+      maker.publicKey,
+    );
+
     await program.methods
-      .purchase()
-      .accounts({
+      .purchase(new anchor.BN(price))
+      .accountsPartial({
         taker: taker.publicKey,
         maker: maker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        treasury,
+        metadata: nftMetadata,
+        makerRewardsAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    // The maker receives the net proceeds plus the rent of the closed listing and vault
+    const makerBalanceAfter = await provider.connection.getBalance(
+      maker.publicKey,
+    );
+    expect(makerBalanceAfter - makerBalanceBefore).to.equal(
+      price - fee - royalty + listingRent + vaultRent,
+    );
+    expect(await provider.connection.getBalance(creator.publicKey)).to.equal(
+      royalty,
+    );
+
+    const treasuryBalanceAfter = await provider.connection.getBalance(treasury);
+    expect(treasuryBalanceAfter - treasuryBalanceBefore).to.equal(fee);
+
+    const takerNft = await getAccount(
+      provider.connection,
+      takerNftAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(Number(takerNft.amount)).to.equal(1);
+
+    // Both sides earn rewards proportional to the sale price
+    for (const ata of [takerRewardsAta, makerRewardsAta]) {
+      const rewards = await getAccount(
+        provider.connection,
+        ata,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(Number(rewards.amount)).to.equal(
+        Math.floor((price * rewardsPerSol.toNumber()) / LAMPORTS_PER_SOL),
+      );
+    }
+  });
+
+  it("Rejects treasury withdrawals from non-admins", async () => {
+    try {
+      await program.methods
+        .withdrawTreasury(new anchor.BN(1))
+        .accountsPartial({ admin: taker.publicKey, marketplace, treasury })
+        .signers([taker])
+        .rpc();
+      expect.fail("Non-admin treasury withdrawal should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("Rejects empty treasury withdrawals", async () => {
+    try {
+      await program.methods
+        .withdrawTreasury(new anchor.BN(0))
+        .accountsPartial({ admin: admin.publicKey, marketplace, treasury })
+        .signers([admin])
+        .rpc();
+      expect.fail("Zero treasury withdrawal should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidWithdrawAmount");
+    }
+  });
+
+  it("Rejects treasury withdrawals that leave it below rent exemption", async () => {
+    const treasuryBalance = await provider.connection.getBalance(treasury);
+
+    try {
+      await program.methods
+        .withdrawTreasury(new anchor.BN(treasuryBalance - 1))
+        .accountsPartial({ admin: admin.publicKey, marketplace, treasury })
+        .signers([admin])
+        .rpc();
+      expect.fail("Treasury withdrawal leaving dust should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("TreasuryBelowRent");
+    }
+  });
+
+  it("Withdraws accumulated fees from the treasury", async () => {
+    const treasuryBalance = await provider.connection.getBalance(treasury);
+    const adminBalanceBefore = await provider.connection.getBalance(
+      admin.publicKey,
+    );
+
+    await program.methods
+      .withdrawTreasury(new anchor.BN(treasuryBalance))
+      .accountsPartial({ admin: admin.publicKey, marketplace, treasury })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    expect(await provider.connection.getBalance(treasury)).to.equal(0);
+    expect(
+      (await provider.connection.getBalance(admin.publicKey)) -
+        adminBalanceBefore,
+    ).to.be.at.least(treasuryBalance - 5000);
+  });
+
+  it("Rejects fee updates above 100%", async () => {
+    try {
+      await program.methods
+        .updateFee(10_001)
+        .accountsPartial({ admin: admin.publicKey, marketplace })
+        .signers([admin])
+        .rpc();
+      expect.fail("Fee above 10000 bps should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidFee");
+    }
+  });
+
+  it("Updates the fee", async () => {
+    await program.methods
+      .updateFee(500)
+      .accountsPartial({ admin: admin.publicKey, marketplace })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    expect((await program.account.marketplace.fetch(marketplace)).fee).to.equal(
+      500,
+    );
+  });
+
+  it("Updates the rewards rate", async () => {
+    await program.methods
+      .updateRewards(rewardsPerSol.muln(2))
+      .accountsPartial({ admin: admin.publicKey, marketplace })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    const { rewardsPerSol: updated } =
+      await program.account.marketplace.fetch(marketplace);
+    expect(updated.toString()).to.equal(rewardsPerSol.muln(2).toString());
+
+    // Restore the original rate for the purchases below
+    await program.methods
+      .updateRewards(rewardsPerSol)
+      .accountsPartial({ admin: admin.publicKey, marketplace })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Rejects transferring the admin role to the current admin", async () => {
+    try {
+      await program.methods
+        .transferAdmin(admin.publicKey)
+        .accountsPartial({ admin: admin.publicKey, marketplace })
+        .signers([admin])
+        .rpc();
+      expect.fail("Transferring to the current admin should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidNewAdmin");
+    }
+  });
+
+  it("Transfers the admin role", async () => {
+    await program.methods
+      .transferAdmin(maker.publicKey)
+      .accountsPartial({ admin: admin.publicKey, marketplace })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    // Nothing changes until the nominated admin accepts
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accountsPartial({ newAdmin: taker.publicKey, marketplace })
+        .signers([taker])
+        .rpc();
+      expect.fail("Only the pending admin should accept the role");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("NotPendingAdmin");
+    }
+    await program.methods
+      .acceptAdmin()
+      .accountsPartial({ newAdmin: maker.publicKey, marketplace })
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    try {
+      await program.methods
+        .updateFee(marketplaceFee)
+        .accountsPartial({ admin: admin.publicKey, marketplace })
+        .signers([admin])
+        .rpc();
+      expect.fail("The previous admin should no longer update the fee");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    // Hand the role back and restore the original fee
+    await program.methods
+      .transferAdmin(admin.publicKey)
+      .accountsPartial({ admin: maker.publicKey, marketplace })
+      .signers([maker])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accountsPartial({ newAdmin: admin.publicKey, marketplace })
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .updateFee(marketplaceFee)
+      .accountsPartial({ admin: admin.publicKey, marketplace })
+      .signers([admin])
+      .rpc();
+  });
+
+  // The taker now owns the NFT and relists it priced in an SPL token, which the
+  // original maker buys back.
+  let paymentMint: PublicKey;
+  const tokenPrice = 100_000_000; // 100 tokens with 6 decimals
+
+  const paymentAta = (owner: PublicKey) =>
+    getAssociatedTokenAddressSync(paymentMint, owner, true, TOKEN_PROGRAM_ID);
+
+  it("List NFT for an SPL token", async () => {
+    paymentMint = await createMint(
+      provider.connection,
+      admin,
+      admin.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    const buyerAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      maker,
+      paymentMint,
+      maker.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    await mintTo(
+      provider.connection,
+      admin,
+      paymentMint,
+      buyerAta.address,
+      admin,
+      tokenPrice,
+      [],
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin,
+      paymentMint,
+      creator.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID,
+    );
+
+    await program.methods
+      .list(new anchor.BN(tokenPrice))
+      .accountsPartial({
+        maker: taker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        makerAta: takerNftAta,
+        vault,
+        listing,
+        collectionMint: collectionMint.publicKey,
+        paymentMint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    const listingAccount = await program.account.listing.fetch(listing);
+    expect(listingAccount.paymentMint.toBase58()).to.equal(
+      paymentMint.toBase58(),
+    );
+  });
+
+  it("Rejects paying for a token listing in SOL", async () => {
+    try {
+      await program.methods
+        .purchase(new anchor.BN(tokenPrice))
+        .accountsPartial({
+          taker: maker.publicKey,
+          maker: taker.publicKey,
+          marketplace,
+          makerMint: nftMint.publicKey,
+          treasury,
+          metadata: nftMetadata,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([maker])
+        .rpc();
+      expect.fail("SOL purchase of a token listing should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("PaymentMintMismatch");
+    }
+  });
+
+  it("Purchase NFT with an SPL token", async () => {
+    const fee = (tokenPrice * marketplaceFee) / 10_000;
+    const royalty = (tokenPrice * royaltyBasisPoints) / 10_000;
+    const rewards = async () =>
+      (
+        await getAccount(
+          provider.connection,
+          makerRewardsAta,
+          undefined,
+          TOKEN_2022_PROGRAM_ID,
+        )
+      ).amount;
+    const buyerRewardsBefore = await rewards();
+
+    await program.methods
+      .purchaseWithToken(new anchor.BN(tokenPrice))
+      .accountsPartial({
+        taker: maker.publicKey,
+        maker: taker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        treasury,
+        paymentMint,
+        metadata: nftMetadata,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        {
+          pubkey: paymentAta(creator.publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    const balance = async (owner: PublicKey) =>
+      Number(
+        (
+          await getAccount(
+            provider.connection,
+            paymentAta(owner),
+            undefined,
+            TOKEN_PROGRAM_ID,
+          )
+        ).amount,
+      );
+
+    expect(await balance(taker.publicKey)).to.equal(
+      tokenPrice - fee - royalty,
+    );
+    expect(await balance(treasury)).to.equal(fee);
+    expect(await balance(creator.publicKey)).to.equal(royalty);
+    expect(await balance(maker.publicKey)).to.equal(0);
+
+    // Rewards are rated per SOL, so a token sale earns the buyer none
+    expect(await rewards()).to.equal(buyerRewardsBefore);
+  });
+
+  it("Withdraws token fees from the treasury", async () => {
+    const fee = (tokenPrice * marketplaceFee) / 10_000;
+
+    await program.methods
+      .withdrawTreasuryToken(new anchor.BN(fee))
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace,
+        treasury,
+        paymentMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    const balance = async (owner: PublicKey) =>
+      Number(
+        (
+          await getAccount(
+            provider.connection,
+            paymentAta(owner),
+            undefined,
+            TOKEN_PROGRAM_ID,
+          )
+        ).amount,
+      );
+
+    expect(await balance(treasury)).to.equal(0);
+    expect(await balance(admin.publicKey)).to.equal(fee);
+  });
+
+  // The maker owns the NFT again, so offers can be accepted straight from their
+  // wallet and, once relisted by the taker, from the listing vault.
+  const offerPda = (bidder: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("offer"),
+        marketplace.toBuffer(),
+        nftMint.publicKey.toBuffer(),
+        bidder.toBuffer(),
+      ],
+      program.programId,
+    )[0];
+
+  it("Rejects an empty offer", async () => {
+    try {
+      await program.methods
+        .makeOffer(new anchor.BN(0))
+        .accountsPartial({
+          bidder: admin.publicKey,
+          marketplace,
+          makerMint: nftMint.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Zero offer should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidOfferAmount");
+    }
+  });
+
+  it("Makes and cancels an offer", async () => {
+    const offer = offerPda(admin.publicKey);
+
+    await program.methods
+      .makeOffer(new anchor.BN(0.5 * LAMPORTS_PER_SOL))
+      .accountsPartial({
+        bidder: admin.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    const offerRent =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        8 + 32 * 2 + 8 + 1,
+      );
+    expect(await provider.connection.getBalance(offer)).to.equal(
+      0.5 * LAMPORTS_PER_SOL + offerRent,
+    );
+
+    await program.methods
+      .cancelOffer()
+      .accountsPartial({
+        bidder: admin.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        offer,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    expect(await provider.connection.getAccountInfo(offer)).to.be.null;
+  });
+
+  it("Accepts an offer on an unlisted NFT", async () => {
+    const amount = 1 * LAMPORTS_PER_SOL;
+    const fee = (amount * marketplaceFee) / 10_000;
+    const royalty = (amount * royaltyBasisPoints) / 10_000;
+    const offer = offerPda(taker.publicKey);
+
+    await program.methods
+      .makeOffer(new anchor.BN(amount))
+      .accountsPartial({
+        bidder: taker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+      })
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    const makerBalanceBefore = await provider.connection.getBalance(
+      maker.publicKey,
+    );
+    const treasuryBalanceBefore =
+      await provider.connection.getBalance(treasury);
+    const creatorBalanceBefore = await provider.connection.getBalance(
+      creator.publicKey,
+    );
+
+    await program.methods
+      .acceptOffer()
+      .accountsPartial({
+        maker: maker.publicKey,
+        bidder: taker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        offer,
+        makerAta: makerNftAta,
+        listing: null,
+        vault: null,
+        approvedCollection: null,
+        treasury,
+        metadata: nftMetadata,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    expect(
+      (await provider.connection.getBalance(maker.publicKey)) -
+        makerBalanceBefore,
+    ).to.equal(amount - fee - royalty);
+    expect(
+      (await provider.connection.getBalance(treasury)) - treasuryBalanceBefore,
+    ).to.equal(fee);
+    expect(
+      (await provider.connection.getBalance(creator.publicKey)) -
+        creatorBalanceBefore,
+    ).to.equal(royalty);
+    expect(await provider.connection.getAccountInfo(offer)).to.be.null;
+
+    const takerNft = await getAccount(
+      provider.connection,
+      takerNftAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(Number(takerNft.amount)).to.equal(1);
+  });
+
+  it("Accepts an offer against a listed NFT", async () => {
+    const offer = offerPda(maker.publicKey);
+
+    await program.methods
+      .list(new anchor.BN(2 * LAMPORTS_PER_SOL))
+      .accountsPartial({
+        maker: taker.publicKey,
+        marketplace,
         makerMint: nftMint.publicKey,
+        makerAta: takerNftAta,
+        vault,
+        listing,
+        collectionMint: collectionMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
       })
       .signers([taker])
       .rpc()
       .then(log);
+
+    await program.methods
+      .makeOffer(new anchor.BN(1 * LAMPORTS_PER_SOL))
+      .accountsPartial({
+        bidder: maker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+      })
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    await program.methods
+      .acceptOffer()
+      .accountsPartial({
+        maker: taker.publicKey,
+        bidder: maker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        offer,
+        makerAta: null,
+        listing,
+        vault,
+        approvedCollection: null,
+        treasury,
+        metadata: nftMetadata,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    expect(await provider.connection.getAccountInfo(listing)).to.be.null;
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+
+    const makerNft = await getAccount(
+      provider.connection,
+      makerNftAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(Number(makerNft.amount)).to.equal(1);
+  });
+
+  const [collectionBid] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("collection_bid"),
+      marketplace.toBuffer(),
+      collectionMint.publicKey.toBuffer(),
+      admin.publicKey.toBuffer(),
+    ],
+    program.programId,
+  );
+  const bidPrice = 0.5 * LAMPORTS_PER_SOL;
+
+  it("Rejects a collection bid for zero items", async () => {
+    try {
+      await program.methods
+        .makeCollectionBid(new anchor.BN(bidPrice), 0)
+        .accountsPartial({
+          bidder: admin.publicKey,
+          marketplace,
+          collectionMint: collectionMint.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Collection bid for zero items should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidBid");
+    }
+  });
+
+  it("Bids on two NFTs from the collection", async () => {
+    await program.methods
+      .makeCollectionBid(new anchor.BN(bidPrice), 2)
+      .accountsPartial({
+        bidder: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    const bidRent =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        8 + 32 * 2 + 8 + 4 + 1,
+      );
+    expect(await provider.connection.getBalance(collectionBid)).to.equal(
+      2 * bidPrice + bidRent,
+    );
+  });
+
+  it("Fills the collection bid with an NFT from the collection", async () => {
+    const fee = (bidPrice * marketplaceFee) / 10_000;
+    const royalty = (bidPrice * royaltyBasisPoints) / 10_000;
+    const makerBalanceBefore = await provider.connection.getBalance(
+      maker.publicKey,
+    );
+    const adminNftAta = getAssociatedTokenAddressSync(
+      nftMint.publicKey,
+      admin.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    await program.methods
+      .fillCollectionBid()
+      .accountsPartial({
+        maker: maker.publicKey,
+        bidder: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+        makerMint: nftMint.publicKey,
+        makerAta: makerNftAta,
+        bidderAta: adminNftAta,
+        collectionBid,
+        treasury,
+        metadata: nftMetadata,
+        masterEdition: nftMasterEdition,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    const bid = await program.account.collectionBid.fetch(collectionBid);
+    expect(bid.quantity).to.equal(1);
+
+    // The maker also pays rent for the bidder's new token account
+    const ataRent = await provider.connection.getBalance(adminNftAta);
+    expect(
+      (await provider.connection.getBalance(maker.publicKey)) -
+        makerBalanceBefore,
+    ).to.equal(bidPrice - fee - royalty - ataRent);
+
+    const adminNft = await getAccount(
+      provider.connection,
+      adminNftAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(Number(adminNft.amount)).to.equal(1);
+  });
+
+  it("Cancels the rest of the collection bid", async () => {
+    await program.methods
+      .cancelCollectionBid()
+      .accountsPartial({
+        bidder: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+        collectionBid,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    expect(await provider.connection.getAccountInfo(collectionBid)).to.be.null;
+  });
+
+  // The admin now owns the NFT and auctions it with a short anti-sniping window
+  const adminNftAta = getAssociatedTokenAddressSync(
+    nftMint.publicKey,
+    admin.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID,
+  );
+  const reservePrice = 1 * LAMPORTS_PER_SOL;
+  const winningBid = 1.2 * LAMPORTS_PER_SOL;
+  const bidAccounts = (
+    bidder: PublicKey,
+    previousBidder: PublicKey | null,
+  ) => ({
+    bidder,
+    marketplace,
+    makerMint: nftMint.publicKey,
+    listing,
+    previousBidder,
+  });
+
+  it("Lists the NFT as an English auction", async () => {
+    await program.methods
+      .listAuction(
+        new anchor.BN(reservePrice),
+        new anchor.BN(10), // duration in seconds
+        new anchor.BN(15), // bids extend the end to 15 seconds out
+      )
+      .accountsPartial({
+        maker: admin.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        makerAta: adminNftAta,
+        vault,
+        listing,
+        collectionMint: collectionMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    const listingAccount = await program.account.listing.fetch(listing);
+    expect(listingAccount.auction.highestBidder).to.be.null;
+  });
+
+  it("Rejects bids below the reserve", async () => {
+    try {
+      await program.methods
+        .placeBid(new anchor.BN(reservePrice - 1))
+        .accountsPartial(bidAccounts(taker.publicKey, null))
+        .signers([taker])
+        .rpc();
+      expect.fail("Bid below the reserve should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("BidTooLow");
+    }
+  });
+
+  it("Rejects fixed-price purchases of an auction", async () => {
+    try {
+      await program.methods
+        .purchase(new anchor.BN(reservePrice))
+        .accountsPartial({
+          taker: taker.publicKey,
+          maker: admin.publicKey,
+          marketplace,
+          makerMint: nftMint.publicKey,
+          treasury,
+          metadata: nftMetadata,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([taker])
+        .rpc();
+      expect.fail("Purchasing an auction listing should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("AuctionListing");
+    }
+  });
+
+  it("Refunds the outbid bidder and extends the auction", async () => {
+    await program.methods
+      .placeBid(new anchor.BN(reservePrice))
+      .accountsPartial(bidAccounts(taker.publicKey, null))
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    const { auction: before } = await program.account.listing.fetch(listing);
+    const takerBalanceBefore = await provider.connection.getBalance(
+      taker.publicKey,
+    );
+
+    await program.methods
+      .placeBid(new anchor.BN(winningBid))
+      .accountsPartial(bidAccounts(maker.publicKey, taker.publicKey))
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    expect(
+      (await provider.connection.getBalance(taker.publicKey)) -
+        takerBalanceBefore,
+    ).to.equal(reservePrice);
+
+    const { auction: after } = await program.account.listing.fetch(listing);
+    expect(after.highestBidder.toBase58()).to.equal(maker.publicKey.toBase58());
+    expect(after.highestBid.toNumber()).to.equal(winningBid);
+    expect(after.endTime.toNumber()).to.be.greaterThan(
+      before.endTime.toNumber(),
+    );
+  });
+
+  const settleAccounts = () => ({
+    payer: taker.publicKey,
+    maker: admin.publicKey,
+    recipient: maker.publicKey,
+    marketplace,
+    makerMint: nftMint.publicKey,
+    recipientAta: makerNftAta,
+    vault,
+    listing,
+    treasury,
+    metadata: nftMetadata,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+  });
+  const creatorAccounts = [
+    { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+  ];
+
+  it("Rejects settling before the auction ends", async () => {
+    try {
+      await program.methods
+        .settle()
+        .accountsPartial(settleAccounts())
+        .remainingAccounts(creatorAccounts)
+        .signers([taker])
+        .rpc();
+      expect.fail("Settling a running auction should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("AuctionNotEnded");
+    }
+  });
+
+  it("Settles the auction to the highest bidder", async () => {
+    const { auction } = await program.account.listing.fetch(listing);
+    const waitMs = auction.endTime.toNumber() * 1000 - Date.now() + 2000;
+    await new Promise((resolve) => setTimeout(resolve, Math.max(waitMs, 0)));
+
+    const fee = (winningBid * marketplaceFee) / 10_000;
+    const royalty = (winningBid * royaltyBasisPoints) / 10_000;
+    const listingRent =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(listing)).data.length,
+      );
+    const vaultRent = await provider.connection.getBalance(vault);
+    const adminBalanceBefore = await provider.connection.getBalance(
+      admin.publicKey,
+    );
+
+    // Anyone can settle; the taker pays for the transaction here
+    await program.methods
+      .settle()
+      .accountsPartial(settleAccounts())
+      .remainingAccounts(creatorAccounts)
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    expect(
+      (await provider.connection.getBalance(admin.publicKey)) -
+        adminBalanceBefore,
+    ).to.equal(winningBid - fee - royalty + listingRent + vaultRent);
+    expect(await provider.connection.getAccountInfo(listing)).to.be.null;
+
+    const makerNft = await getAccount(
+      provider.connection,
+      makerNftAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(Number(makerNft.amount)).to.equal(1);
+  });
+
+  const listAccounts = () => ({
+    maker: maker.publicKey,
+    marketplace,
+    makerMint: nftMint.publicKey,
+    makerAta: makerNftAta,
+    vault,
+    listing,
+    collectionMint: collectionMint.publicKey,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+  });
+
+  it("Rejects listings and bids from collections that are not approved", async () => {
+    await program.methods
+      .revokeCollection()
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    try {
+      await program.methods
+        .list(new anchor.BN(LAMPORTS_PER_SOL))
+        .accountsPartial(listAccounts())
+        .signers([maker])
+        .rpc();
+      expect.fail("Listing from a revoked collection should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+
+    try {
+      await program.methods
+        .makeCollectionBid(new anchor.BN(bidPrice), 1)
+        .accountsPartial({
+          bidder: admin.publicKey,
+          marketplace,
+          collectionMint: collectionMint.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Bidding on a revoked collection should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Charges the collection fee override on sales", async () => {
+    const price = 1 * LAMPORTS_PER_SOL;
+    const feeOverride = 100; // 1%
+
+    await program.methods
+      .approveCollection(feeOverride)
+      .accountsPartial({
+        admin: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    await program.methods
+      .list(new anchor.BN(price))
+      .accountsPartial(listAccounts())
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    const listingAccount = await program.account.listing.fetch(listing);
+    expect(listingAccount.feeOverride).to.equal(feeOverride);
+
+    const treasuryBalanceBefore =
+      await provider.connection.getBalance(treasury);
+    const makerRewardsBefore = await getAccount(
+      provider.connection,
+      makerRewardsAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    // Leaving out the maker's rewards ATA skips the seller reward instead of
+    // reverting the purchase
+    await program.methods
+      .purchase(new anchor.BN(price))
+      .accountsPartial({
+        taker: taker.publicKey,
+        maker: maker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        treasury,
+        metadata: nftMetadata,
+        makerRewardsAta: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    expect(
+      (await provider.connection.getBalance(treasury)) - treasuryBalanceBefore,
+    ).to.equal((price * feeOverride) / 10_000);
+    const makerRewardsAfter = await getAccount(
+      provider.connection,
+      makerRewardsAta,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    expect(makerRewardsAfter.amount).to.equal(makerRewardsBefore.amount);
+  });
+
+  it("Charges the collection fee override on collection bid fills", async () => {
+    const feeOverride = 100; // 1%
+
+    await program.methods
+      .makeCollectionBid(new anchor.BN(bidPrice), 1)
+      .accountsPartial({
+        bidder: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+      })
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    const treasuryBalanceBefore =
+      await provider.connection.getBalance(treasury);
+
+    await program.methods
+      .fillCollectionBid()
+      .accountsPartial({
+        maker: taker.publicKey,
+        bidder: admin.publicKey,
+        marketplace,
+        collectionMint: collectionMint.publicKey,
+        makerMint: nftMint.publicKey,
+        makerAta: takerNftAta,
+        bidderAta: getAssociatedTokenAddressSync(
+          nftMint.publicKey,
+          admin.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+        collectionBid,
+        treasury,
+        metadata: nftMetadata,
+        masterEdition: nftMasterEdition,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    expect(
+      (await provider.connection.getBalance(treasury)) - treasuryBalanceBefore,
+    ).to.equal((bidPrice * feeOverride) / 10_000);
+    expect(await provider.connection.getAccountInfo(collectionBid)).to.be.null;
+  });
+
+  it("Charges the collection fee override on offers for unlisted NFTs", async () => {
+    const amount = 1 * LAMPORTS_PER_SOL;
+    const feeOverride = 100; // 1%
+    const offer = offerPda(taker.publicKey);
+    const [approvedCollection] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("collection"),
+        marketplace.toBuffer(),
+        collectionMint.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+
+    // The admin filled the collection bid above and now holds the NFT unlisted
+    await program.methods
+      .makeOffer(new anchor.BN(amount))
+      .accountsPartial({
+        bidder: taker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+      })
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    const treasuryBalanceBefore =
+      await provider.connection.getBalance(treasury);
+
+    await program.methods
+      .acceptOffer()
+      .accountsPartial({
+        maker: admin.publicKey,
+        bidder: taker.publicKey,
+        marketplace,
+        makerMint: nftMint.publicKey,
+        offer,
+        makerAta: getAssociatedTokenAddressSync(
+          nftMint.publicKey,
+          admin.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+        listing: null,
+        vault: null,
+        approvedCollection,
+        treasury,
+        metadata: nftMetadata,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([admin])
+      .rpc()
+      .then(log);
+
+    expect(
+      (await provider.connection.getBalance(treasury)) - treasuryBalanceBefore,
+    ).to.equal((amount * feeOverride) / 10_000);
+  });
+
+  // Compressed NFTs live in a small tree with no canopy. The only leaf is at
+  // index 0, so its proof is always the empty node at each level.
+  const merkleTree = Keypair.generate();
+  const maxDepth = 3;
+  const [treeConfig] = PublicKey.findProgramAddressSync(
+    [merkleTree.publicKey.toBuffer()],
+    BUBBLEGUM_PROGRAM_ID,
+  );
+  const cnftCreators = [
+    { address: creator.publicKey, verified: true, share: 100 },
+  ];
+  // Minting into the collection verifies it, so the leaf hashes the verified
+  // collection
+  const cnftMetadata: MetadataArgs = {
+    name: "Compressed NFT",
+    symbol: "CNFT",
+    uri: "https://example.com/cnft.json",
+    sellerFeeBasisPoints: royaltyBasisPoints,
+    primarySaleHappened: false,
+    isMutable: true,
+    editionNonce: null,
+    tokenStandard: TokenStandard.NonFungible,
+    collection: { key: collectionMint.publicKey, verified: true },
+    uses: null,
+    tokenProgramVersion: TokenProgramVersion.Original,
+    creators: cnftCreators,
+  };
+  // The same metadata in the program's IDL encoding, which spells enums as
+  // objects
+  const cnftMetadataArgs = {
+    ...cnftMetadata,
+    tokenStandard: { nonFungible: {} },
+    tokenProgramVersion: { original: {} },
+  };
+  const cnftProof = [...Array(maxDepth).keys()].map((level) => ({
+    pubkey: new PublicKey(emptyNode(level)),
+    isSigner: false,
+    isWritable: false,
+  }));
+  let assetId: PublicKey;
+  let cnftListing: PublicKey;
+
+  const currentRoot = async (): Promise<number[]> => {
+    const tree = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+      provider.connection,
+      merkleTree.publicKey,
+    );
+    return Array.from(tree.getCurrentRoot());
+  };
+
+  const cnftAccounts = () => ({
+    marketplace,
+    treeConfig,
+    merkleTree: merkleTree.publicKey,
+    logWrapper: SPL_NOOP_PROGRAM_ID,
+    compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+    bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+  });
+
+  const listCnft = async (price: number, id = assetId) =>
+    program.methods
+      .listCnft(
+        id,
+        new anchor.BN(price),
+        {
+          root: await currentRoot(),
+          dataHash: Array.from(computeDataHash(cnftMetadata)),
+          creatorHash: Array.from(computeCreatorHash(cnftCreators)),
+          nonce: new anchor.BN(0),
+          index: 0,
+        },
+        cnftMetadataArgs,
+      )
+      .accountsPartial({
+        ...cnftAccounts(),
+        maker: maker.publicKey,
+        collectionMint: collectionMint.publicKey,
+      })
+      .remainingAccounts(cnftProof)
+      .signers([maker])
+      .rpc();
+
+  it("Mints a compressed NFT to the maker", async () => {
+    const tx = new Transaction().add(
+      await createAllocTreeIx(
+        provider.connection,
+        merkleTree.publicKey,
+        provider.wallet.publicKey,
+        { maxDepth, maxBufferSize: 8 },
+        0,
+      ),
+      createCreateTreeInstruction(
+        {
+          treeAuthority: treeConfig,
+          merkleTree: merkleTree.publicKey,
+          payer: provider.wallet.publicKey,
+          treeCreator: provider.wallet.publicKey,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        },
+        { maxDepth, maxBufferSize: 8, public: false },
+      ),
+    );
+    const mintIx = createMintToCollectionV1Instruction(
+      {
+        treeAuthority: treeConfig,
+        leafOwner: maker.publicKey,
+        leafDelegate: maker.publicKey,
+        merkleTree: merkleTree.publicKey,
+        payer: provider.wallet.publicKey,
+        treeDelegate: provider.wallet.publicKey,
+        collectionAuthority: admin.publicKey,
+        collectionAuthorityRecordPda: BUBBLEGUM_PROGRAM_ID,
+        collectionMint: collectionMint.publicKey,
+        collectionMetadata,
+        editionAccount: collectionMasterEdition,
+        bubblegumSigner: PublicKey.findProgramAddressSync(
+          [Buffer.from("collection_cpi")],
+          BUBBLEGUM_PROGRAM_ID,
+        )[0],
+        logWrapper: SPL_NOOP_PROGRAM_ID,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+      },
+      {
+        metadataArgs: {
+          ...cnftMetadata,
+          collection: { key: collectionMint.publicKey, verified: false },
+        },
+      },
+    );
+    // The creator signs to be minted as verified
+    mintIx.keys.push({
+      pubkey: creator.publicKey,
+      isSigner: true,
+      isWritable: false,
+    });
+    tx.add(mintIx);
+    await provider.sendAndConfirm(tx, [merkleTree, admin, creator]).then(log);
+
+    assetId = await getLeafAssetId(merkleTree.publicKey, new anchor.BN(0));
+    [cnftListing] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cnft_listing"), marketplace.toBuffer(), assetId.toBuffer()],
+      program.programId,
+    );
+  });
+
+  it("Rejects compressed listings with the wrong asset id", async () => {
+    try {
+      await listCnft(LAMPORTS_PER_SOL, Keypair.generate().publicKey);
+      expect.fail("Listing under a mismatched asset id should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidAssetId");
+    }
+  });
+
+  it("Lists and delists a compressed NFT", async () => {
+    await listCnft(LAMPORTS_PER_SOL).then(log);
+
+    const listingAccount = await program.account.cnftListing.fetch(cnftListing);
+    expect(listingAccount.maker.toBase58()).to.equal(
+      maker.publicKey.toBase58(),
+    );
+    expect(listingAccount.assetId.toBase58()).to.equal(assetId.toBase58());
+
+    await program.methods
+      .delistCnft(await currentRoot())
+      .accountsPartial({
+        ...cnftAccounts(),
+        maker: maker.publicKey,
+        listing: cnftListing,
+      })
+      .remainingAccounts(cnftProof)
+      .signers([maker])
+      .rpc()
+      .then(log);
+
+    expect(await provider.connection.getAccountInfo(cnftListing)).to.be.null;
+  });
+
+  it("Rejects compressed listings from collections that are not approved", async () => {
+    const otherCollection = Keypair.generate().publicKey;
+
+    try {
+      await program.methods
+        .listCnft(
+          assetId,
+          new anchor.BN(LAMPORTS_PER_SOL),
+          {
+            root: await currentRoot(),
+            dataHash: Array.from(computeDataHash(cnftMetadata)),
+            creatorHash: Array.from(computeCreatorHash(cnftCreators)),
+            nonce: new anchor.BN(0),
+            index: 0,
+          },
+          cnftMetadataArgs,
+        )
+        .accountsPartial({
+          ...cnftAccounts(),
+          maker: maker.publicKey,
+          collectionMint: otherCollection,
+        })
+        .remainingAccounts(cnftProof)
+        .signers([maker])
+        .rpc();
+      expect.fail("Listing from an unapproved collection should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Rejects compressed listings whose metadata does not match the leaf", async () => {
+    try {
+      await program.methods
+        .listCnft(
+          assetId,
+          new anchor.BN(LAMPORTS_PER_SOL),
+          {
+            root: await currentRoot(),
+            dataHash: Array.from(computeDataHash(cnftMetadata)),
+            creatorHash: Array.from(computeCreatorHash(cnftCreators)),
+            nonce: new anchor.BN(0),
+            index: 0,
+          },
+          { ...cnftMetadataArgs, name: "Forged" },
+        )
+        .accountsPartial({
+          ...cnftAccounts(),
+          maker: maker.publicKey,
+          collectionMint: collectionMint.publicKey,
+        })
+        .remainingAccounts(cnftProof)
+        .signers([maker])
+        .rpc();
+      expect.fail("Listing with forged metadata should fail");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InvalidCnftMetadata");
+    }
+  });
+
+  it("Purchases a compressed NFT", async () => {
+    const price = 1 * LAMPORTS_PER_SOL;
+    await listCnft(price).then(log);
+
+    // The listing carries the collection's fee override
+    const listingAccount = await program.account.cnftListing.fetch(cnftListing);
+    const fee = (price * listingAccount.feeOverride) / 10_000;
+    const royalty = (price * royaltyBasisPoints) / 10_000;
+    const rewards = Math.floor(
+      (price * rewardsPerSol.toNumber()) / LAMPORTS_PER_SOL,
+    );
+    const rewardsBalance = async (ata: PublicKey) =>
+      Number(
+        (
+          await getAccount(
+            provider.connection,
+            ata,
+            undefined,
+            TOKEN_2022_PROGRAM_ID,
+          )
+        ).amount,
+      );
+
+    const treasuryBalanceBefore =
+      await provider.connection.getBalance(treasury);
+    const creatorBalanceBefore = await provider.connection.getBalance(
+      creator.publicKey,
+    );
+    const takerRewardsBefore = await rewardsBalance(takerRewardsAta);
+    const makerRewardsBefore = await rewardsBalance(makerRewardsAta);
+
+    await program.methods
+      .purchaseCnft(new anchor.BN(price), await currentRoot(), cnftCreators)
+      .accountsPartial({
+        ...cnftAccounts(),
+        taker: taker.publicKey,
+        maker: maker.publicKey,
+        listing: cnftListing,
+        treasury,
+        rewardMint,
+        makerRewardsAta,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: creator.publicKey, isSigner: false, isWritable: true },
+        ...cnftProof,
+      ])
+      .signers([taker])
+      .rpc()
+      .then(log);
+
+    expect(await provider.connection.getAccountInfo(cnftListing)).to.be.null;
+    expect(
+      (await provider.connection.getBalance(treasury)) - treasuryBalanceBefore,
+    ).to.equal(fee);
+    expect(
+      (await provider.connection.getBalance(creator.publicKey)) -
+        creatorBalanceBefore,
+    ).to.equal(royalty);
+    expect(await rewardsBalance(takerRewardsAta)).to.equal(
+      takerRewardsBefore + rewards,
+    );
+    expect(await rewardsBalance(makerRewardsAta)).to.equal(
+      makerRewardsBefore + rewards,
+    );
   });
 });